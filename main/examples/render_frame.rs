@@ -0,0 +1,49 @@
+// 原生渲染第一关的某一帧并输出 PNG，可选与基准图比较
+//
+// cargo run --example render_frame -- <ms> <out.png> [golden.png]
+
+use std::{env, path::Path, process};
+
+use plants_vs_zombies::{
+    data::{CANVAS_HEIGHT, CANVAS_WIDTH},
+    headless::Headless,
+    renderer::software::{RgbaImage, SoftwareRenderer},
+    scene::model::SceneKind,
+};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 3 {
+        eprintln!("usage: render_frame <ms> <out.png> [golden.png]");
+        process::exit(2);
+    }
+
+    let duration: f64 = args[1].parse().expect("<ms> should be a number");
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let renderer = SoftwareRenderer::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+    let frame = renderer.frame();
+    let mut headless = Headless::with_renderer(Box::new(renderer));
+
+    headless.load(&root).expect("failed to load assets");
+    headless.load_sheets(&root);
+    headless.game.first_scene = SceneKind::Battle;
+    headless.start();
+    headless.run_for(duration, 1000.0 / 60.0);
+
+    let frame = frame.borrow();
+
+    frame
+        .save(Path::new(&args[2]))
+        .expect("failed to write frame");
+
+    if let Some(golden) = args.get(3) {
+        let golden = RgbaImage::open(Path::new(golden)).expect("failed to read golden image");
+        let diff = frame.diff(&golden, 2);
+
+        if diff > 0 {
+            eprintln!("{} pixels differ from {}", diff, args[3]);
+            process::exit(1);
+        }
+    }
+}
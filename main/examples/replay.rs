@@ -0,0 +1,48 @@
+// 原生回放录像文件并输出最后一帧，可选与基准图比较
+//
+// cargo run --example replay -- <replay.json> <out.png> [golden.png]
+
+use std::{env, fs, path::Path, process};
+
+use plants_vs_zombies::{
+    data::{CANVAS_HEIGHT, CANVAS_WIDTH},
+    headless::Headless,
+    renderer::software::{RgbaImage, SoftwareRenderer},
+    replay::Replay,
+};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 3 {
+        eprintln!("usage: replay <replay.json> <out.png> [golden.png]");
+        process::exit(2);
+    }
+
+    let json = fs::read_to_string(&args[1]).expect("failed to read replay");
+    let replay = Replay::new_from_json(&json).expect("invalid replay file");
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let renderer = SoftwareRenderer::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+    let frame = renderer.frame();
+    let mut headless = Headless::with_renderer(Box::new(renderer));
+
+    headless.load(&root).expect("failed to load assets");
+    headless.load_sheets(&root);
+    headless.replay(&replay).expect("failed to replay");
+
+    let frame = frame.borrow();
+
+    frame
+        .save(Path::new(&args[2]))
+        .expect("failed to write frame");
+
+    if let Some(golden) = args.get(3) {
+        let golden = RgbaImage::open(Path::new(golden)).expect("failed to read golden image");
+        let diff = frame.diff(&golden, 2);
+
+        if diff > 0 {
+            eprintln!("{} pixels differ from {}", diff, args[3]);
+            process::exit(1);
+        }
+    }
+}
@@ -1,17 +1,17 @@
 use std::{mem, rc::Rc};
 
-use web_sys::{CanvasRenderingContext2d, HtmlImageElement};
-
 use crate::{
-    game::Game,
+    data::OUTLINE_EPSILON,
     marching_squares::MarchingSquares,
+    polygon::Polygon,
+    renderer::{Image, Renderer},
     sprites::model::{CollisionMargin, Pos, SpriteCell},
 };
 
-use super::model::{Draw, Resource, Stroke};
+use super::model::{ArtistState, Draw, Resource, Stroke};
 
 pub struct Artist {
-    pub image: Rc<HtmlImageElement>,
+    pub image: Option<Rc<dyn Image>>,
     pub cell_index: usize,
     pub cells: Vec<SpriteCell>,
     original_cells: Vec<SpriteCell>,
@@ -20,7 +20,7 @@ pub struct Artist {
 }
 
 impl Artist {
-    pub fn new(image: Rc<HtmlImageElement>, cells: Vec<SpriteCell>, scale: f64) -> Artist {
+    pub fn new(image: Option<Rc<dyn Image>>, cells: Vec<SpriteCell>, scale: f64) -> Artist {
         Artist {
             image,
             cell_index: 0,
@@ -31,94 +31,75 @@ impl Artist {
         }
     }
 
-    pub fn execute_draw_image(
-        context: &CanvasRenderingContext2d,
-        image: &HtmlImageElement,
-        pos: &Pos,
-        cell: &SpriteCell,
-        scale: f64,
-    ) {
-        context
-            .draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
-                image,
-                cell.left,
-                cell.top,
-                cell.width,
-                cell.height,
-                pos.left,
-                pos.top,
-                cell.width * scale,
-                cell.height * scale,
-            )
-            .unwrap();
-    }
-
     pub fn get_image_outline_points2(
-        image: &HtmlImageElement,
+        image: Option<&dyn Image>,
         cell: &SpriteCell,
         offset_x: f64,
         offset_y: f64,
         normal_shape: bool,
         scale: f64,
     ) -> Vec<Pos> {
-        if normal_shape {
-            return vec![
-                Pos::new(offset_x, offset_y),
-                Pos::new(offset_x + cell.width * scale, offset_y),
-                Pos::new(
-                    offset_x + cell.width * scale,
-                    offset_y + cell.height * scale,
-                ),
-                Pos::new(offset_x, offset_y + cell.height * scale),
-            ];
-        }
-
-        let width = cell.width as u32;
-        let height = cell.height as u32;
-        let offscreen_canvas = Game::create_canvas(width, height);
-        let offscreen_context = Game::get_canvas_context(&offscreen_canvas);
-        let pos = Pos::new(0.0, 0.0);
+        let image_data = match image {
+            Some(image) if !normal_shape => image.read_pixels(cell),
+            _ => None,
+        };
 
-        Artist::execute_draw_image(&offscreen_context, image, &pos, &cell, 1.0);
+        // 按帧内坐标计算并简化，再缩放、平移到精灵的位置
+        if let Some(image_data) = image_data {
+            let marching_squares = MarchingSquares::new(0.0, 0.0);
+            let points = marching_squares.get(&image_data, cell.width as i32, cell.height as i32);
+
+            return Artist::place_outline(
+                &Polygon::simplify(&points, OUTLINE_EPSILON),
+                offset_x,
+                offset_y,
+                scale,
+            );
+        }
 
-        let image_data = offscreen_context
-            .get_image_data(0.0, 0.0, width as f64, height as f64)
-            .unwrap()
-            .data();
-        let marching_squares = MarchingSquares::new(offset_x, offset_y);
-        let outline_points = marching_squares.get(&image_data, width as i32, height as i32);
+        vec![
+            Pos::new(offset_x, offset_y),
+            Pos::new(offset_x + cell.width * scale, offset_y),
+            Pos::new(
+                offset_x + cell.width * scale,
+                offset_y + cell.height * scale,
+            ),
+            Pos::new(offset_x, offset_y + cell.height * scale),
+        ]
+    }
 
-        return outline_points;
+    // 帧内坐标的轮廓按精灵的缩放比例放到 offset 处，预先计算的轮廓同样如此
+    pub fn place_outline(points: &[Pos], offset_x: f64, offset_y: f64, scale: f64) -> Vec<Pos> {
+        points
+            .iter()
+            .map(|point| Pos::new(offset_x + point.left * scale, offset_y + point.top * scale))
+            .collect()
     }
 
-    pub fn draw_image(&self, context: &CanvasRenderingContext2d, pos: &Pos, cell: &SpriteCell) {
-        Artist::execute_draw_image(context, self.image.as_ref(), pos, cell, self.scale);
+    pub fn draw_image(&self, renderer: &mut dyn Renderer, pos: &Pos, cell: &SpriteCell) {
+        if let Some(image) = &self.image {
+            renderer.draw_image(image.as_ref(), cell, pos, self.scale);
+        }
     }
 
     pub fn get_image_outline_points(&self, pos: &Pos) -> Vec<Pos> {
-        let offscreen_canvas = Game::create_canvas(900, 600);
-        let offscreen_context = Game::get_canvas_context(&offscreen_canvas);
         let cell = self.get_current_cell();
 
-        if let Some(cell) = cell {
-            self.draw_image(&offscreen_context, pos, cell);
-
-            let (width, height) = self.width_height(&offscreen_context);
-            let image_data = offscreen_context
-                .get_image_data(0.0, 0.0, width, height)
-                .unwrap()
-                .data();
-            let marching_squares = MarchingSquares::new(0.0, 0.0);
-            let outline_points = marching_squares.get(&image_data, width as i32, height as i32);
-
-            return outline_points;
-        } else {
-            return vec![];
+        match cell {
+            Some(cell) => Artist::get_image_outline_points2(
+                self.image.as_deref(),
+                cell,
+                pos.left,
+                pos.top,
+                false,
+                self.scale,
+            ),
+            None => vec![],
         }
     }
 
     fn in_cell(&self, index: usize) -> bool {
-        return self.cell_index == index;
+        self.cell_index == index
     }
 
     fn swap_cell(&mut self) {
@@ -154,17 +135,12 @@ impl Resource for Artist {
 }
 
 impl Draw for Artist {
-    fn draw(
-        &self,
-        context: &CanvasRenderingContext2d,
-        pos: &Pos,
-        collision_margin: &CollisionMargin,
-    ) {
+    fn draw(&self, renderer: &mut dyn Renderer, pos: &Pos, collision_margin: &CollisionMargin) {
         let cell = self.get_current_cell();
 
         if let Some(cell) = cell {
-            self.draw_image(context, pos, cell);
-            self.rect(context, pos, cell, self.scale, collision_margin);
+            self.draw_image(renderer, pos, cell);
+            self.rect(renderer, pos, cell, self.scale, collision_margin);
         }
     }
 
@@ -181,4 +157,22 @@ impl Draw for Artist {
     fn revert(&mut self) {
         self.swap_cell();
     }
+
+    fn save(&self) -> ArtistState {
+        ArtistState {
+            cell_index: self.cell_index,
+            cells: self.cells.to_vec(),
+            original_index: self.original_index,
+            original_cells: self.original_cells.to_vec(),
+            scale: self.scale,
+        }
+    }
+
+    fn load(&mut self, state: &ArtistState) {
+        self.cell_index = state.cell_index;
+        self.cells.clone_from(&state.cells);
+        self.original_index = state.original_index;
+        self.original_cells.clone_from(&state.original_cells);
+        self.scale = state.scale;
+    }
 }
@@ -1,8 +1,9 @@
-use web_sys::CanvasRenderingContext2d;
-
 use derives::Resource;
 
-use crate::sprites::model::{CollisionMargin, Pos, SpriteCell};
+use crate::{
+    renderer::Renderer,
+    sprites::model::{CollisionMargin, Pos, SpriteCell},
+};
 
 use super::{
     artist::Artist,
@@ -24,17 +25,12 @@ impl HoverArtist {
 }
 
 impl Draw for HoverArtist {
-    fn draw(
-        &self,
-        context: &CanvasRenderingContext2d,
-        pos: &Pos,
-        _collision_margin: &CollisionMargin,
-    ) {
+    fn draw(&self, renderer: &mut dyn Renderer, pos: &Pos, _collision_margin: &CollisionMargin) {
         let cell = self.artist.get_current_cell();
 
         if let Some(cell) = cell {
-            self.artist.draw_image(context, pos, cell);
-            self.outline(context, &self.points);
+            self.artist.draw_image(renderer, pos, cell);
+            self.outline(renderer, &self.points);
         }
     }
 }
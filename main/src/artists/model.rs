@@ -1,11 +1,14 @@
-use web_sys::CanvasRenderingContext2d;
+use serde::{Deserialize, Serialize};
 
-use crate::sprites::model::{CollisionMargin, Pos, SpriteCell};
+use crate::{
+    renderer::Renderer,
+    sprites::model::{CollisionMargin, Pos, SpriteCell},
+};
 
 pub trait Stroke {
     fn rect(
         &self,
-        context: &CanvasRenderingContext2d,
+        renderer: &mut dyn Renderer,
         pos: &Pos,
         cell: &SpriteCell,
         scale: f64,
@@ -19,43 +22,33 @@ pub trait Stroke {
             bottom,
         } = collision_margin;
 
-        context.save();
+        renderer.save();
 
-        context.set_stroke_style(&"#00CCFF".into());
-        context.stroke_rect(
+        renderer.set_stroke_style("#00CCFF");
+        renderer.stroke_rect(
             pos.left + left,
             pos.top + top,
             width - right - left,
             height - bottom - top,
         );
 
-        context.set_stroke_style(&"#FF0000".into());
-        context.stroke_rect(pos.left, pos.top, width, height);
+        renderer.set_stroke_style("#FF0000");
+        renderer.stroke_rect(pos.left, pos.top, width, height);
 
-        context.restore();
+        renderer.restore();
     }
 
-    fn outline(&self, context: &CanvasRenderingContext2d, points: &Vec<Pos>) {
-        if points.len() == 0 {
+    fn outline(&self, renderer: &mut dyn Renderer, points: &[Pos]) {
+        if points.is_empty() {
             return;
         }
 
-        context.save();
+        renderer.save();
 
-        context.begin_path();
-        context.set_stroke_style(&"#00CCFF".into());
+        renderer.set_stroke_style("#00CCFF");
+        renderer.stroke_polygon(points);
 
-        context.move_to(points[0].left, points[1].top);
-
-        points
-            .iter()
-            .skip(1)
-            .for_each(|path| context.line_to(path.left, path.top));
-
-        context.close_path();
-        context.stroke();
-
-        context.restore();
+        renderer.restore();
     }
 }
 
@@ -74,30 +67,27 @@ pub trait Resource {
 }
 
 pub trait Draw: Resource {
-    fn draw(
-        &self,
-        context: &CanvasRenderingContext2d,
-        pos: &Pos,
-        collision_margin: &CollisionMargin,
-    );
+    fn draw(&self, renderer: &mut dyn Renderer, pos: &Pos, collision_margin: &CollisionMargin);
 
-    fn width_height(&self, context: &CanvasRenderingContext2d) -> (f64, f64) {
-        let canvas = context.canvas().unwrap();
-        let width = canvas.width() as f64;
-        let height = canvas.height() as f64;
+    fn update_scale(&mut self, _scale: f64) {}
 
-        return (width, height);
-    }
+    fn switch(&mut self, _cells: &Vec<SpriteCell>) {}
 
-    fn clear(&self, context: &CanvasRenderingContext2d) {
-        let (width, height) = self.width_height(context);
+    fn revert(&mut self) {}
 
-        context.clear_rect(0.0, 0.0, width, height);
+    // 快照：当前帧与切换前的帧序列
+    fn save(&self) -> ArtistState {
+        Default::default()
     }
 
-    fn update_scale(&mut self, _scale: f64) {}
-
-    fn switch(&mut self, _cells: &Vec<SpriteCell>) {}
+    fn load(&mut self, _state: &ArtistState) {}
+}
 
-    fn revert(&mut self) {}
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ArtistState {
+    pub cell_index: usize,
+    pub cells: Vec<SpriteCell>,
+    pub original_index: usize,
+    pub original_cells: Vec<SpriteCell>,
+    pub scale: f64,
 }
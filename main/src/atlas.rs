@@ -0,0 +1,327 @@
+use std::collections::BTreeMap;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::sprites::model::{CellTrim, SpriteCell};
+
+/* AtlasFrame */
+// 精灵图中的一帧，name 为导出工具中的原始名称，如 ConeheadZombieWalk_05.png
+#[derive(Debug, Clone)]
+pub struct AtlasFrame {
+    pub name: String,
+    pub cell: SpriteCell,
+}
+
+/* Atlas */
+// TexturePacker 导出的精灵图描述，支持 Sparrow/Starling 的 XML 与 JSON（hash 或 array）
+#[derive(Debug, Clone, Default)]
+pub struct Atlas {
+    pub frames: Vec<AtlasFrame>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRect {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonSize {
+    w: f64,
+    h: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonPivot {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonFrame {
+    #[serde(default)]
+    filename: String,
+    frame: JsonRect,
+    #[serde(default)]
+    rotated: bool,
+    #[serde(default)]
+    trimmed: bool,
+    #[serde(rename = "spriteSourceSize")]
+    sprite_source_size: Option<JsonRect>,
+    #[serde(rename = "sourceSize")]
+    source_size: Option<JsonSize>,
+    pivot: Option<JsonPivot>,
+}
+
+// hash 格式按文件中的顺序读取，没有帧序号的帧保持导出时的顺序
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JsonFrames {
+    Hash(IndexMap<String, JsonFrame>),
+    Array(Vec<JsonFrame>),
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonAtlas {
+    frames: JsonFrames,
+}
+
+impl Atlas {
+    // 根据内容判断格式
+    pub fn parse(text: &str) -> Result<Atlas, String> {
+        match text.trim_start().starts_with('{') {
+            true => Atlas::from_json(text),
+            false => Atlas::from_xml(text),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Atlas, String> {
+        let atlas: JsonAtlas = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let frames = match atlas.frames {
+            JsonFrames::Hash(frames) => frames
+                .into_iter()
+                .map(|(name, frame)| JsonFrame {
+                    filename: name,
+                    ..frame
+                })
+                .collect(),
+            JsonFrames::Array(frames) => frames,
+        };
+        let mut result: Vec<AtlasFrame> = vec![];
+
+        for frame in frames {
+            let JsonFrame {
+                filename,
+                frame: rect,
+                rotated,
+                trimmed,
+                sprite_source_size,
+                source_size,
+                pivot,
+            } = frame;
+
+            if rotated {
+                return Err(format!("rotated frame {} is not supported", filename));
+            }
+
+            let mut cell = SpriteCell::new(rect.x, rect.y, rect.w, rect.h);
+
+            if let (true, Some(offset), Some(size)) = (trimmed, sprite_source_size, source_size) {
+                cell.trim = Some(CellTrim {
+                    x: offset.x,
+                    y: offset.y,
+                    width: size.w,
+                    height: size.h,
+                });
+            }
+
+            cell.pivot = pivot.map(|pivot| (pivot.x, pivot.y));
+
+            result.push(AtlasFrame {
+                name: filename,
+                cell,
+            });
+        }
+
+        Ok(Atlas { frames: result })
+    }
+
+    // 只读取 SubTexture 的属性，坐标可以是 x/y 或 left/top
+    pub fn from_xml(xml: &str) -> Result<Atlas, String> {
+        let mut frames: Vec<AtlasFrame> = vec![];
+        let mut rest = xml;
+
+        while let Some(start) = rest.find("<SubTexture") {
+            let tag = &rest[start + "<SubTexture".len()..];
+            let end = Atlas::tag_end(tag).ok_or_else(|| "unclosed SubTexture tag".to_string())?;
+            let attrs = Atlas::attributes(&tag[..end])?;
+            let name = attrs
+                .get("name")
+                .cloned()
+                .ok_or_else(|| "SubTexture without name".to_string())?;
+            let number = |keys: &[&str], default: Option<f64>| -> Result<f64, String> {
+                match keys.iter().find_map(|key| attrs.get(*key)) {
+                    Some(value) => value
+                        .parse::<f64>()
+                        .map_err(|_| format!("invalid {} \"{}\" of {}", keys[0], value, name)),
+                    None => default.ok_or_else(|| format!("missing {} of {}", keys[0], name)),
+                }
+            };
+
+            if attrs.get("rotated").map(String::as_str) == Some("true") {
+                return Err(format!("rotated frame {} is not supported", name));
+            }
+
+            let mut cell = SpriteCell::new(
+                number(&["x", "left"], None)?,
+                number(&["y", "top"], None)?,
+                number(&["width"], None)?,
+                number(&["height"], None)?,
+            );
+
+            // frameX、frameY 为负数，表示裁剪掉的左、上边宽度
+            if attrs.contains_key("frameWidth") {
+                cell.trim = Some(CellTrim {
+                    x: -number(&["frameX"], Some(0.0))?,
+                    y: -number(&["frameY"], Some(0.0))?,
+                    width: number(&["frameWidth"], None)?,
+                    height: number(&["frameHeight"], Some(cell.height))?,
+                });
+            }
+
+            // XML 中的锚点为像素，统一成比例
+            if attrs.contains_key("pivotX") || attrs.contains_key("pivotY") {
+                let (width, height) = match cell.trim {
+                    Some(trim) => (trim.width, trim.height),
+                    None => (cell.width, cell.height),
+                };
+
+                cell.pivot = Some((
+                    number(&["pivotX"], Some(width / 2.0))? / width,
+                    number(&["pivotY"], Some(height / 2.0))? / height,
+                ));
+            }
+
+            frames.push(AtlasFrame { name, cell });
+            rest = &tag[end..];
+        }
+
+        Ok(Atlas { frames })
+    }
+
+    // 标签结束的 >，跳过引号内的字符
+    fn tag_end(tag: &str) -> Option<usize> {
+        let mut quote = None;
+
+        for (index, c) in tag.char_indices() {
+            match (quote, c) {
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '>') => return Some(index),
+                (Some(open), _) if open == c => quote = None,
+                _ => (),
+            }
+        }
+
+        None
+    }
+
+    fn attributes(tag: &str) -> Result<BTreeMap<String, String>, String> {
+        let mut attrs = BTreeMap::new();
+        let mut rest = tag;
+
+        while let Some(eq) = rest.find('=') {
+            let key = rest[..eq].trim().to_string();
+            let value = rest[eq + 1..].trim_start();
+            let quote = value
+                .chars()
+                .next()
+                .filter(|c| *c == '"' || *c == '\'')
+                .ok_or_else(|| format!("unquoted attribute {}", key))?;
+            let end = value[1..]
+                .find(quote)
+                .ok_or_else(|| format!("unclosed attribute {}", key))?;
+
+            attrs.insert(key, Atlas::unescape(&value[1..end + 1]));
+            rest = &value[end + 2..];
+        }
+
+        Ok(attrs)
+    }
+
+    fn unescape(value: &str) -> String {
+        value
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
+    }
+
+    // 去掉扩展名与末尾的帧序号，如 ConeheadZombieWalk_05.png 为 (ConeheadZombieWalk, 5)，
+    // Peashooter/0001.png 为 (Peashooter, 1)；序号必须用 _ - / 或空格隔开，SunFlower1 是独立的动画
+    pub fn split_name(name: &str) -> (String, Option<usize>) {
+        let stem = match name.rfind('.') {
+            Some(dot) if !name[dot + 1..].contains('/') => &name[..dot],
+            _ => name,
+        };
+        let digits = stem.len() - stem.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        let (group, index) = stem.split_at(stem.len() - digits);
+
+        match (group.chars().last(), index.parse::<usize>()) {
+            (Some('_' | '-' | '/' | ' '), Ok(index)) if group.len() > 1 => {
+                (group[..group.len() - 1].to_string(), Some(index))
+            }
+            _ => (stem.to_string(), None),
+        }
+    }
+
+    // 按动画分组，组内按帧序号排序，没有序号的保持原有顺序；键名加上精灵图名称前缀
+    pub fn to_cells(&self, sheet_name: &str) -> BTreeMap<String, Vec<SpriteCell>> {
+        let mut groups: BTreeMap<String, Vec<(Option<usize>, SpriteCell)>> = BTreeMap::new();
+
+        for frame in self.frames.iter() {
+            let (group, index) = Atlas::split_name(&frame.name);
+
+            groups
+                .entry(format!("{}/{}", sheet_name, group))
+                .or_default()
+                .push((index, frame.cell));
+        }
+
+        groups
+            .into_iter()
+            .map(|(name, mut cells)| {
+                cells.sort_by_key(|(index, _)| *index);
+
+                (name, cells.into_iter().map(|(_, cell)| cell).collect())
+            })
+            .collect()
+    }
+
+    // 与 assets/jsons 下的帧数据文件相同的格式：每帧一行
+    pub fn write_cells(cells: &BTreeMap<String, Vec<SpriteCell>>) -> String {
+        let groups: Vec<String> = cells
+            .iter()
+            .map(|(name, cells)| {
+                let lines: Vec<String> = cells
+                    .iter()
+                    .map(|cell| format!("    {}", Atlas::write_cell(cell)))
+                    .collect();
+
+                format!(
+                    "  {}: [\n{}\n  ]",
+                    Value::from(name.as_str()),
+                    lines.join(",\n")
+                )
+            })
+            .collect();
+
+        format!("{{\n{}\n}}\n", groups.join(",\n"))
+    }
+
+    fn write_cell(cell: &SpriteCell) -> String {
+        let mut fields = vec![
+            format!("\"left\": {}", cell.left),
+            format!("\"top\": {}", cell.top),
+            format!("\"width\": {}", cell.width),
+            format!("\"height\": {}", cell.height),
+        ];
+
+        if let Some(trim) = cell.trim {
+            fields.push(format!(
+                "\"trim\": {{ \"x\": {}, \"y\": {}, \"width\": {}, \"height\": {} }}",
+                trim.x, trim.y, trim.width, trim.height
+            ));
+        }
+
+        if let Some((x, y)) = cell.pivot {
+            fields.push(format!("\"pivot\": [{}, {}]", x, y));
+        }
+
+        format!("{{ {} }}", fields.join(", "))
+    }
+}
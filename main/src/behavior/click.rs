@@ -1,20 +1,23 @@
-use web_sys::CanvasRenderingContext2d;
+use std::mem;
 
 use crate::{
-    artists::model::Stroke,
-    callback::ErasedFnPointer,
     model::{Interface, SpriteType},
-    sprites::model::{DrawInfo, Pos, SpriteCell, Update},
+    polygon::Polygon,
+    sprites::{
+        components::{Clickable, Transform},
+        model::{Pos, SpriteCell},
+        sprite::Sprite,
+    },
 };
 
-use super::model::{Behavior, BehaviorType};
+use super::model::{Behavior, BehaviorData, BehaviorState, BehaviorType};
 
 pub struct ClickBehavior {
     name: BehaviorType,
     working: bool,
     clicked: bool,
     points: Vec<Pos>,
-    cb: Option<ErasedFnPointer>,
+    fired: bool,
 }
 
 impl ClickBehavior {
@@ -24,36 +27,29 @@ impl ClickBehavior {
             points,
             clicked: false,
             working: false,
-            cb: None,
+            fired: false,
         }
     }
 
-    pub fn execute_callback(&self) {
-        match self.cb {
-            Some(cb) if self.clicked => cb.call(),
-            _ => (),
-        }
+    pub fn fire(&mut self) {
+        self.fired = self.clicked;
     }
 
-    pub fn update_points(&mut self, sprite: &dyn Update) {
-        match sprite.name() {
-            SpriteType::Interface(Interface::Sun) => {
-                let DrawInfo {
-                    pos: Pos { left, top },
-                    ..
-                } = sprite.get_draw_info().unwrap();
-                let SpriteCell { width, height, .. } =
-                    sprite.get_read_artist().get_current_cell().unwrap();
-                let new_points: Vec<Pos> = vec![
-                    Pos::new(*left, *top),
-                    Pos::new(left + width, *top),
-                    Pos::new(left + width, top + height),
-                    Pos::new(*left, top + height),
-                ];
-
-                self.points = new_points;
-            }
-            _ => (),
+    pub fn update_points(&mut self, sprite: &Sprite) {
+        if let SpriteType::Interface(Interface::Sun) = sprite.name {
+            let Transform {
+                pos: Pos { left, top },
+                ..
+            } = &sprite.transform;
+            let SpriteCell { width, height, .. } = sprite.artist.get_current_cell().unwrap();
+            let new_points: Vec<Pos> = vec![
+                Pos::new(*left, *top),
+                Pos::new(left + width, *top),
+                Pos::new(left + width, top + height),
+                Pos::new(*left, top + height),
+            ];
+
+            self.points = new_points;
         }
     }
 }
@@ -61,29 +57,56 @@ impl ClickBehavior {
 impl Behavior for ClickBehavior {
     fn execute(
         &mut self,
-        sprite: &mut dyn Update,
+        sprite: &mut Sprite,
         now: f64,
         _last_animation_frame_time: f64,
         mouse_pos: &Pos,
-        context: &CanvasRenderingContext2d,
     ) {
         self.update_points(sprite);
+        self.clicked = Polygon::contains(&self.points, mouse_pos);
 
-        let Pos { left, top } = mouse_pos;
-
-        self.outline(context, &self.points);
-        self.clicked = context.is_point_in_path_with_f64(*left, *top);
-
-        sprite.set_clicked(self.clicked);
+        if let Some(clickable) = &mut sprite.clickable {
+            *clickable = Clickable {
+                clicked: self.clicked,
+            };
+        }
 
         self.stop(now);
-        self.execute_callback();
+        self.fire();
     }
 
     fn name(&self) -> BehaviorType {
         self.name
     }
 
+    fn reuse(&mut self, _data: &BehaviorData, offset: &Pos) {
+        for point in &mut self.points {
+            point.left += offset.left;
+            point.top += offset.top;
+        }
+    }
+
+    fn save(&self) -> BehaviorState {
+        BehaviorState::Click {
+            working: self.working,
+            clicked: self.clicked,
+            points: self.points.clone(),
+        }
+    }
+
+    fn load(&mut self, state: &BehaviorState) {
+        if let BehaviorState::Click {
+            working,
+            clicked,
+            points,
+        } = state
+        {
+            self.working = *working;
+            self.clicked = *clicked;
+            self.points = points.clone();
+        }
+    }
+
     fn is_working(&mut self) -> bool {
         self.working
     }
@@ -96,9 +119,7 @@ impl Behavior for ClickBehavior {
         self.working = false;
     }
 
-    fn set_cb(&mut self, cb: ErasedFnPointer) {
-        self.cb = Some(cb);
+    fn take_fired(&mut self) -> bool {
+        mem::replace(&mut self.fired, false)
     }
 }
-
-impl Stroke for ClickBehavior {}
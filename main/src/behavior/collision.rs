@@ -1,19 +1,16 @@
-use web_sys::CanvasRenderingContext2d;
+use crate::sprites::{model::Pos, sprite::Sprite};
 
-use crate::{
-    callback::ErasedFnPointer,
-    sprites::{
-        model::{Pos, Update},
-        sprite::Sprite,
-    },
-};
-
-use super::model::{Behavior, BehaviorType};
+use super::model::{Behavior, BehaviorState, BehaviorType};
 
 pub struct CollisionBehavior {
     name: BehaviorType,
     working: bool,
-    cb: Option<ErasedFnPointer>,
+}
+
+impl Default for CollisionBehavior {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CollisionBehavior {
@@ -21,37 +18,27 @@ impl CollisionBehavior {
         CollisionBehavior {
             name: BehaviorType::Collision,
             working: false,
-            cb: None,
         }
     }
 
-    pub fn is_candidate_for_collision(sprite: &Sprite, other_sprite: &Box<dyn Update>) -> bool {
-        let (row, col) = (sprite.row, sprite.col);
+    pub fn is_candidate_for_collision(sprite: &Sprite, other_sprite: &Sprite) -> bool {
+        let (row, col) = sprite.get_loc();
         let (o_row, o_col) = other_sprite.get_loc();
 
         row == o_row && col != 0 && (o_col == col - 1)
     }
 
-    pub fn did_collide(sprite: &Sprite, other_sprite: &Box<dyn Update>) -> bool {
-        let pos = sprite.draw_info.pos;
-        let collision_margin = sprite.collision_margin;
-        let collision_left = pos.left + collision_margin.left;
-        let cell = other_sprite.get_read_artist().get_current_cell();
-        let draw_info = other_sprite.get_draw_info();
-
-        match (cell, draw_info) {
-            (Some(cell), Some(draw_info)) => {
-                let o_pos = draw_info.pos;
-
-                collision_left >= o_pos.left && collision_left <= o_pos.left + cell.width
-            }
-            _ => false,
-        }
-    }
-
-    fn execute_callback(&self) {
-        if let Some(cb) = &self.cb {
-            cb.call();
+    pub fn did_collide(sprite: &Sprite, other_sprite: &Sprite) -> bool {
+        let pos = sprite.transform.pos;
+        let collision_left = match sprite.collider {
+            Some(collider) => pos.left + collider.margin.left,
+            None => pos.left,
+        };
+        let o_pos = other_sprite.transform.pos;
+
+        match other_sprite.artist.get_current_cell() {
+            Some(cell) => collision_left >= o_pos.left && collision_left <= o_pos.left + cell.width,
+            None => false,
         }
     }
 }
@@ -59,14 +46,13 @@ impl CollisionBehavior {
 impl Behavior for CollisionBehavior {
     fn execute(
         &mut self,
-        sprite: &mut dyn Update,
+        sprite: &mut Sprite,
         now: f64,
         _last_animation_frame_time: f64,
         _mouse_pos: &Pos,
-        _context: &CanvasRenderingContext2d,
     ) {
+        // 碰撞由 Game::check_collision 检测并产生 Collided 事件，这里只在碰撞后停止
         if sprite.is_collision() {
-            self.execute_callback();
             self.stop(now);
         }
     }
@@ -75,6 +61,18 @@ impl Behavior for CollisionBehavior {
         self.name
     }
 
+    fn save(&self) -> BehaviorState {
+        BehaviorState::Collision {
+            working: self.working,
+        }
+    }
+
+    fn load(&mut self, state: &BehaviorState) {
+        if let BehaviorState::Collision { working } = state {
+            self.working = *working;
+        }
+    }
+
     fn is_working(&mut self) -> bool {
         self.working
     }
@@ -86,8 +84,4 @@ impl Behavior for CollisionBehavior {
     fn stop(&mut self, _now: f64) {
         self.working = false;
     }
-
-    fn set_cb(&mut self, cb: ErasedFnPointer) {
-        self.cb = Some(cb);
-    }
 }
@@ -1,8 +1,6 @@
-use web_sys::CanvasRenderingContext2d;
+use crate::sprites::{model::Pos, sprite::Sprite};
 
-use crate::sprites::model::{Pos, Update};
-
-use super::model::{Behavior, BehaviorType};
+use super::model::{Behavior, BehaviorState, BehaviorType};
 
 pub struct DragBehavior {
     name: BehaviorType,
@@ -10,6 +8,12 @@ pub struct DragBehavior {
     last_pos: Option<Pos>,
 }
 
+impl Default for DragBehavior {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DragBehavior {
     pub fn new() -> DragBehavior {
         DragBehavior {
@@ -39,17 +43,16 @@ impl DragBehavior {
 impl Behavior for DragBehavior {
     fn execute(
         &mut self,
-        sprite: &mut dyn Update,
+        sprite: &mut Sprite,
         _now: f64,
         _last_animation_frame_time: f64,
         mouse_pos: &Pos,
-        _context: &CanvasRenderingContext2d,
     ) {
         if sprite.is_clicked() {
-            let draw_info = sprite.get_draw_info().unwrap();
-            let new_pos = self.cal_pos_offset(&draw_info.pos, mouse_pos.clone());
+            let draw_info = &sprite.transform;
+            let new_pos = self.cal_pos_offset(&draw_info.pos, *mouse_pos);
 
-            sprite.update_draw_info(Some(new_pos), None);
+            sprite.update_transform(Some(new_pos), None);
         }
     }
 
@@ -57,6 +60,20 @@ impl Behavior for DragBehavior {
         self.name
     }
 
+    fn save(&self) -> BehaviorState {
+        BehaviorState::Drag {
+            working: self.working,
+            last_pos: self.last_pos,
+        }
+    }
+
+    fn load(&mut self, state: &BehaviorState) {
+        if let BehaviorState::Drag { working, last_pos } = state {
+            self.working = *working;
+            self.last_pos = *last_pos;
+        }
+    }
+
     fn is_working(&mut self) -> bool {
         self.working
     }
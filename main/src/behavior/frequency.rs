@@ -1,23 +1,21 @@
-use web_sys::CanvasRenderingContext2d;
+use std::mem;
 
 use crate::{
-    callback::ErasedFnPointer,
-    sprites::model::{Pos, Update},
+    sprites::{components::Animation, model::Pos, sprite::Sprite},
+    systems,
     timer::animation_timer::AnimationTimer,
 };
 
-use super::{
-    cycle::CycleBehavior,
-    model::{Behavior, BehaviorType},
-};
+use super::model::{Behavior, BehaviorState, BehaviorType};
 
 pub struct FrequencyBehavior {
     name: BehaviorType,
     total: usize,
-    cycle: CycleBehavior,
+    animation: Animation,
+    timer: AnimationTimer,
     count: usize,
     delay_execute_callback: f64,
-    cb: Option<ErasedFnPointer>,
+    fired: bool,
 }
 
 impl FrequencyBehavior {
@@ -26,9 +24,10 @@ impl FrequencyBehavior {
             count: 0,
             total: 2,
             name: BehaviorType::Frequency,
-            cycle: CycleBehavior::new(duration, None),
+            animation: Animation::new(duration, None),
+            timer: AnimationTimer::new(duration),
             delay_execute_callback: 1000.0,
-            cb: None,
+            fired: false,
         }
     }
 
@@ -37,59 +36,76 @@ impl FrequencyBehavior {
     }
 
     fn should_execute_callback(&self, now: f64) -> bool {
-        now - self.cycle.last_advance > self.delay_execute_callback
+        now - self.animation.last_advance > self.delay_execute_callback
     }
 
-    fn execute_callback(&self) {
-        if let Some(cb) = &self.cb {
-            cb.call();
-        }
+    fn fire(&mut self) {
+        self.fired = true;
     }
 }
 
 impl Behavior for FrequencyBehavior {
     fn execute(
         &mut self,
-        sprite: &mut dyn Update,
+        sprite: &mut Sprite,
         now: f64,
-        last_animation_frame_time: f64,
-        mouse_pos: &Pos,
-        context: &CanvasRenderingContext2d,
+        _last_animation_frame_time: f64,
+        _mouse_pos: &Pos,
     ) {
-        let artist = sprite.get_artist();
+        let artist = sprite.artist.as_mut();
 
         match self.finished() {
             true if self.should_execute_callback(now) => {
                 sprite.toggle();
 
-                self.execute_callback();
-                self.cycle.stop(now);
+                self.fire();
+                self.stop(now);
             }
             true => (),
             false => {
-                let one_frame_passed = now - self.cycle.last_advance > self.cycle.duration;
+                let one_frame_passed = now - self.animation.last_advance > self.animation.duration;
 
                 if artist.in_last_cell() && one_frame_passed {
                     self.count += 1;
                 }
 
                 if !self.finished() {
-                    self.cycle
-                        .execute(sprite, now, last_animation_frame_time, mouse_pos, context);
+                    systems::advance(&mut self.animation, artist, now);
                 }
             }
         }
     }
 
     fn get_timer(&mut self) -> Option<&mut AnimationTimer> {
-        self.cycle.get_timer()
+        Some(&mut self.timer)
     }
 
-    fn set_cb(&mut self, cb: ErasedFnPointer) {
-        self.cb = Some(cb);
+    fn take_fired(&mut self) -> bool {
+        mem::replace(&mut self.fired, false)
     }
 
     fn name(&self) -> BehaviorType {
         self.name
     }
+
+    fn save(&self) -> BehaviorState {
+        BehaviorState::Frequency {
+            count: self.count,
+            animation: self.animation,
+            timer: self.timer.save(),
+        }
+    }
+
+    fn load(&mut self, state: &BehaviorState) {
+        if let BehaviorState::Frequency {
+            count,
+            animation,
+            timer,
+        } = state
+        {
+            self.count = *count;
+            self.animation = *animation;
+            self.timer.load(timer);
+        }
+    }
 }
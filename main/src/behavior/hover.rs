@@ -1,11 +1,9 @@
-use web_sys::CanvasRenderingContext2d;
-
 use crate::{
-    artists::model::Stroke,
-    sprites::model::{Pos, Update},
+    polygon::Polygon,
+    sprites::{model::Pos, sprite::Sprite},
 };
 
-use super::model::{Behavior, BehaviorType};
+use super::model::{Behavior, BehaviorState, BehaviorType};
 
 #[derive(Debug)]
 pub struct HoverBehavior {
@@ -31,18 +29,13 @@ impl HoverBehavior {
 impl Behavior for HoverBehavior {
     fn execute(
         &mut self,
-        sprite: &mut dyn Update,
+        sprite: &mut Sprite,
         _now: f64,
         _last_animation_frame_time: f64,
         mouse_pos: &Pos,
-        context: &CanvasRenderingContext2d,
     ) {
-        let Pos { left, top } = mouse_pos;
-
-        self.outline(context, &self.points);
-
-        let inpath = context.is_point_in_path_with_f64(*left, *top);
-        let artist = sprite.get_artist();
+        let inpath = Polygon::contains(&self.points, mouse_pos);
+        let artist = sprite.artist.as_mut();
 
         self.enter = inpath;
 
@@ -63,6 +56,27 @@ impl Behavior for HoverBehavior {
         self.name
     }
 
+    fn save(&self) -> BehaviorState {
+        BehaviorState::Hover {
+            working: self.working,
+            enter: self.enter,
+            moving: self.moving,
+        }
+    }
+
+    fn load(&mut self, state: &BehaviorState) {
+        if let BehaviorState::Hover {
+            working,
+            enter,
+            moving,
+        } = state
+        {
+            self.working = *working;
+            self.enter = *enter;
+            self.moving = *moving;
+        }
+    }
+
     fn is_working(&mut self) -> bool {
         self.working
     }
@@ -75,5 +89,3 @@ impl Behavior for HoverBehavior {
         self.working = false;
     }
 }
-
-impl Stroke for HoverBehavior {}
@@ -1,11 +1,13 @@
-use crate::{callback::ErasedFnPointer, timer::animation_timer::AnimationTimer};
+use std::mem;
 
-use super::model::{Behavior, BehaviorType};
+use crate::timer::animation_timer::AnimationTimer;
+
+use super::model::{Behavior, BehaviorState, BehaviorType};
 
 pub struct IntervalBehavior {
     name: BehaviorType,
     timer: AnimationTimer,
-    cb: Option<ErasedFnPointer>,
+    fired: bool,
 }
 
 impl IntervalBehavior {
@@ -13,29 +15,26 @@ impl IntervalBehavior {
         IntervalBehavior {
             name: BehaviorType::Interval,
             timer: AnimationTimer::new(interval),
-            cb: None,
+            fired: false,
         }
     }
 
-    fn execute_callback(&self) {
-        if let Some(cb) = &self.cb {
-            cb.call();
-        }
+    fn fire(&mut self) {
+        self.fired = true;
     }
 }
 
 impl Behavior for IntervalBehavior {
     fn execute(
         &mut self,
-        _sprite: &mut dyn crate::sprites::model::Update,
+        _sprite: &mut crate::sprites::sprite::Sprite,
         now: f64,
         _last_animation_frame_time: f64,
         _mouse_pos: &crate::sprites::model::Pos,
-        _context: &web_sys::CanvasRenderingContext2d,
     ) {
         if self.timer.is_expired(now) {
             self.start(now);
-            self.execute_callback();
+            self.fire();
         }
     }
 
@@ -43,11 +42,23 @@ impl Behavior for IntervalBehavior {
         self.name
     }
 
+    fn save(&self) -> BehaviorState {
+        BehaviorState::Interval {
+            timer: self.timer.save(),
+        }
+    }
+
+    fn load(&mut self, state: &BehaviorState) {
+        if let BehaviorState::Interval { timer } = state {
+            self.timer.load(timer);
+        }
+    }
+
     fn get_timer(&mut self) -> Option<&mut AnimationTimer> {
         Some(&mut self.timer)
     }
 
-    fn set_cb(&mut self, cb: ErasedFnPointer) {
-        self.cb = Some(cb);
+    fn take_fired(&mut self) -> bool {
+        mem::replace(&mut self.fired, false)
     }
 }
@@ -1,7 +1,5 @@
-pub mod Interval;
 pub mod click;
 pub mod collision;
-pub mod cycle;
 pub mod drag;
 pub mod frequency;
 pub mod hover;
@@ -1,22 +1,21 @@
-use serde::Deserialize;
-use web_sys::CanvasRenderingContext2d;
-
 use crate::{
-    callback::ErasedFnPointer,
-    sprites::model::{Pos, Update},
-    timer::{animation_timer::AnimationTimer, model::Time},
+    sprites::{components::Animation, model::Pos, sprite::Sprite},
+    timer::{
+        animation_timer::{AnimationTimer, AnimationTimerState},
+        model::Time,
+    },
 };
+use serde::{Deserialize, Serialize};
 
 /* *************** trait *************** */
 
 pub trait Behavior {
     fn execute(
         &mut self,
-        sprite: &mut dyn Update,
+        sprite: &mut Sprite,
         now: f64,
         last_animation_frame_time: f64,
         mouse_pos: &Pos,
-        context: &CanvasRenderingContext2d,
     );
 
     fn get_timer(&mut self) -> Option<&mut AnimationTimer> {
@@ -35,21 +34,46 @@ pub trait Behavior {
         }
     }
 
+    // 暂停期间计时器不计时，恢复后从暂停前的进度继续
+    fn pause(&mut self, now: f64) {
+        if let Some(timer) = self.get_timer() {
+            timer.pause(now)
+        }
+    }
+
+    fn unpause(&mut self, now: f64) {
+        if let Some(timer) = self.get_timer() {
+            timer.unpause(now)
+        }
+    }
+
     fn is_working(&mut self) -> bool {
-        return match self.get_timer() {
+        match self.get_timer() {
             Some(timer) => timer.is_working(),
             None => true,
-        };
+        }
     }
 
-    fn set_cb(&mut self, _cb: ErasedFnPointer) {}
+    // 返回并清除本帧的触发标记，由 SpriteWrap 转成事件放入队列
+    fn take_fired(&mut self) -> bool {
+        false
+    }
 
     fn name(&self) -> BehaviorType;
+
+    // 对象池复用精灵时调用：按新的数据更新配置，与位置相关的状态平移 offset
+    fn reuse(&mut self, _data: &BehaviorData, _offset: &Pos) {}
+
+    // 快照：只保存运行时状态，配置由精灵数据重新构建
+    fn save(&self) -> BehaviorState;
+
+    fn load(&mut self, state: &BehaviorState);
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum BehaviorType {
     Hover,
+    // 数据中的 Cycle 构建为 Animation 组件，不创建行为
     Cycle,
     Walk,
     Switch,
@@ -61,19 +85,14 @@ pub enum BehaviorType {
     Interval,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
 pub enum Direction {
+    #[default]
     Horizontal,
     Vertical,
 }
 
-impl Default for Direction {
-    fn default() -> Self {
-        Direction::Horizontal
-    }
-}
-
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BehaviorData {
     pub name: BehaviorType,
     #[serde(default)]
@@ -94,6 +113,48 @@ pub struct BehaviorData {
     pub direction: Direction,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BehaviorState {
+    Click {
+        working: bool,
+        clicked: bool,
+        points: Vec<Pos>,
+    },
+    Collision {
+        working: bool,
+    },
+    Drag {
+        working: bool,
+        last_pos: Option<Pos>,
+    },
+    Frequency {
+        count: usize,
+        animation: Animation,
+        timer: AnimationTimerState,
+    },
+    Hover {
+        working: bool,
+        enter: bool,
+        moving: bool,
+    },
+    Interval {
+        timer: AnimationTimerState,
+    },
+    Scroll {
+        working: bool,
+        offset: f64,
+    },
+    Switch {
+        switched: bool,
+        switch_index: usize,
+        timer: AnimationTimerState,
+    },
+    Walk {
+        offset: f64,
+        timer: AnimationTimerState,
+    },
+}
+
 fn default_normal_shape() -> bool {
     true
 }
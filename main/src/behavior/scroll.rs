@@ -1,12 +1,15 @@
-use web_sys::CanvasRenderingContext2d;
+use std::mem;
 
 use crate::{
-    callback::ErasedFnPointer,
     fps::Fps,
-    sprites::model::{DrawInfo, Offset, Pos, Update},
+    sprites::{
+        components::Transform,
+        model::{Offset, Pos},
+        sprite::Sprite,
+    },
 };
 
-use super::model::{Behavior, BehaviorType};
+use super::model::{Behavior, BehaviorState, BehaviorType};
 
 pub struct ScrollBehavior {
     name: BehaviorType,
@@ -14,7 +17,7 @@ pub struct ScrollBehavior {
     distance: f64,
     offset: f64,
     working: bool,
-    cb: Option<ErasedFnPointer>,
+    fired: bool,
 }
 
 impl ScrollBehavior {
@@ -25,37 +28,34 @@ impl ScrollBehavior {
             distance,
             offset: 0.0,
             working: false,
-            cb: None,
+            fired: false,
         }
     }
 
     fn turn_right(&mut self, now: f64) {
         self.rate = self.rate.abs();
         self.stop(now);
-        self.execute_callback();
+        self.fire();
     }
 
     fn turn_left(&mut self, now: f64) {
         self.rate = -self.rate;
         self.stop(now);
-        self.execute_callback();
+        self.fire();
     }
 
-    fn execute_callback(&self) {
-        if let Some(cb) = &self.cb {
-            cb.call();
-        }
+    fn fire(&mut self) {
+        self.fired = true;
     }
 }
 
 impl Behavior for ScrollBehavior {
     fn execute(
         &mut self,
-        sprite: &mut dyn Update,
+        sprite: &mut Sprite,
         now: f64,
         last_animation_frame_time: f64,
         _mouse_pos: &Pos,
-        _context: &CanvasRenderingContext2d,
     ) {
         let frame_offset = Fps::cal_pixel_frame(self.rate, now, last_animation_frame_time);
 
@@ -67,10 +67,10 @@ impl Behavior for ScrollBehavior {
             false => (),
         }
 
-        let DrawInfo { offset, .. } = *sprite.get_draw_info().unwrap();
+        let Transform { offset, .. } = sprite.transform;
         let new_offset = Offset::new(offset.x + frame_offset, offset.y);
 
-        sprite.update_draw_info(None, Some(new_offset));
+        sprite.update_transform(None, Some(new_offset));
     }
 
     fn is_working(&mut self) -> bool {
@@ -85,11 +85,25 @@ impl Behavior for ScrollBehavior {
         self.working = false;
     }
 
-    fn set_cb(&mut self, cb: ErasedFnPointer) {
-        self.cb = Some(cb);
+    fn take_fired(&mut self) -> bool {
+        mem::replace(&mut self.fired, false)
     }
 
     fn name(&self) -> BehaviorType {
         self.name
     }
+
+    fn save(&self) -> BehaviorState {
+        BehaviorState::Scroll {
+            working: self.working,
+            offset: self.offset,
+        }
+    }
+
+    fn load(&mut self, state: &BehaviorState) {
+        if let BehaviorState::Scroll { working, offset } = state {
+            self.working = *working;
+            self.offset = *offset;
+        }
+    }
 }
@@ -1,12 +1,15 @@
-use web_sys::CanvasRenderingContext2d;
+use std::mem;
 
 use crate::{
-    callback::ErasedFnPointer,
-    sprites::model::{Pos, SpriteCell, Update},
+    sprites::{
+        components::SwitchState,
+        model::{Pos, SpriteCell},
+        sprite::Sprite,
+    },
     timer::animation_timer::AnimationTimer,
 };
 
-use super::model::{Behavior, BehaviorType};
+use super::model::{Behavior, BehaviorState, BehaviorType};
 
 pub struct SwitchBehavior {
     name: BehaviorType,
@@ -15,7 +18,7 @@ pub struct SwitchBehavior {
     switch_index: usize,
     cells: Vec<Vec<SpriteCell>>,
     infinite: bool,
-    cb: Option<ErasedFnPointer>,
+    fired: bool,
 }
 
 impl SwitchBehavior {
@@ -27,13 +30,13 @@ impl SwitchBehavior {
             timer: AnimationTimer::new(duration),
             infinite,
             switched: false,
-            cb: None,
+            fired: false,
         }
     }
 
-    fn update_pos(&mut self, sprite: &mut dyn Update, revert: bool) {
-        let pos = sprite.get_draw_info().unwrap().pos;
-        let artist = sprite.get_artist();
+    fn _update_pos(&mut self, sprite: &mut Sprite, revert: bool) {
+        let pos = sprite.transform.pos;
+        let artist = sprite.artist.as_mut();
         let cur_cell = artist.get_current_cell().unwrap();
         let cur_height = cur_cell.height;
         let height = self.cells[self.switch_index][0].height;
@@ -44,26 +47,26 @@ impl SwitchBehavior {
         };
         let new_pos = Pos::new(pos.left, pos.top + delta_height * dir);
 
-        sprite.update_draw_info(Some(new_pos), None);
+        sprite.update_transform(Some(new_pos), None);
     }
 
-    fn switch(&mut self, sprite: &mut dyn Update, switch_index: usize, now: f64) {
-        let artist = sprite.get_artist();
+    fn switch(&mut self, sprite: &mut Sprite, switch_index: usize, now: f64) {
+        let artist = sprite.artist.as_mut();
 
         artist.switch(&self.cells[switch_index]);
 
-        // self.update_pos(sprite, false);
+        // self._update_pos(sprite, false);
         self.update(switch_index, false, now);
     }
 
-    fn revert(&mut self, sprite: &mut dyn Update, now: f64) {
-        let artist = sprite.get_artist();
+    fn revert(&mut self, sprite: &mut Sprite, now: f64) {
+        let artist = sprite.artist.as_mut();
 
         artist.revert();
 
-        // self.update_pos(sprite, true);
+        // self._update_pos(sprite, true);
         self.update(99, false, now);
-        self.execute_callback();
+        self.fire();
     }
 
     fn update(&mut self, switch_index: usize, switched: bool, now: f64) {
@@ -72,23 +75,23 @@ impl SwitchBehavior {
         self.timer.finisehd(now);
     }
 
-    fn execute_callback(&self) {
-        if let Some(cb) = &self.cb {
-            cb.call();
-        }
+    fn fire(&mut self) {
+        self.fired = true;
     }
 }
 
 impl Behavior for SwitchBehavior {
     fn execute(
         &mut self,
-        sprite: &mut dyn Update,
+        sprite: &mut Sprite,
         now: f64,
         _last_animation_frame_time: f64,
         _mouse_pos: &Pos,
-        _context: &CanvasRenderingContext2d,
     ) {
-        let (trigger, index) = sprite.tirgger_switch();
+        let (trigger, index) = match sprite.switch {
+            Some(SwitchState { active, index }) => (active, index),
+            None => (false, 0),
+        };
 
         self.switched = index == self.switch_index;
 
@@ -103,11 +106,32 @@ impl Behavior for SwitchBehavior {
         Some(&mut self.timer)
     }
 
-    fn set_cb(&mut self, cb: ErasedFnPointer) {
-        self.cb = Some(cb);
+    fn take_fired(&mut self) -> bool {
+        mem::replace(&mut self.fired, false)
     }
 
     fn name(&self) -> BehaviorType {
         self.name
     }
+
+    fn save(&self) -> BehaviorState {
+        BehaviorState::Switch {
+            switched: self.switched,
+            switch_index: self.switch_index,
+            timer: self.timer.save(),
+        }
+    }
+
+    fn load(&mut self, state: &BehaviorState) {
+        if let BehaviorState::Switch {
+            switched,
+            switch_index,
+            timer,
+        } = state
+        {
+            self.switched = *switched;
+            self.switch_index = *switch_index;
+            self.timer.load(timer);
+        }
+    }
 }
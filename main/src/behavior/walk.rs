@@ -1,12 +1,15 @@
 use crate::{
     fps::Fps,
     loc::Loc,
-    model::SpriteType,
-    sprites::model::{DrawInfo, Pos, Update},
+    sprites::{
+        components::{Lane, Transform},
+        model::Pos,
+        sprite::Sprite,
+    },
     timer::animation_timer::AnimationTimer,
 };
 
-use super::model::{Behavior, BehaviorType, Direction};
+use super::model::{Behavior, BehaviorData, BehaviorState, BehaviorType, Direction};
 
 pub struct WalkBehavior {
     name: BehaviorType,
@@ -29,20 +32,20 @@ impl WalkBehavior {
         }
     }
 
-    fn update_loc(&self, pos: &Pos, sprite: &mut dyn Update) {
-        let cell = sprite.get_artist().get_current_cell().unwrap();
+    fn update_loc(&self, pos: &Pos, sprite: &mut Sprite) {
+        let cell = sprite.artist.get_current_cell().unwrap();
         let zombie_center_pos = Pos::new(pos.left + cell.width / 2.0, pos.top + cell.height / 2.0);
         let (row, col) = Loc::get_row_col_by_pos(&zombie_center_pos);
 
-        sprite.update_loc(row, col);
+        sprite.lane = Some(Lane::new(row, col));
     }
 
-    fn get_latest_pos(&self, sprite: &mut dyn Update, frame_offset: f64) -> Pos {
+    fn get_latest_pos(&self, sprite: &mut Sprite, frame_offset: f64) -> Pos {
         let is_vertical = self.direction == Direction::Vertical;
-        let DrawInfo {
+        let Transform {
             pos: Pos { left, top },
             ..
-        } = *sprite.get_draw_info().unwrap();
+        } = sprite.transform;
         let (left, top) = match is_vertical {
             true => (left, top + frame_offset),
             false => {
@@ -55,18 +58,17 @@ impl WalkBehavior {
             }
         };
 
-        return Pos::new(left, top);
+        Pos::new(left, top)
     }
 }
 
 impl Behavior for WalkBehavior {
     fn execute(
         &mut self,
-        sprite: &mut dyn Update,
+        sprite: &mut Sprite,
         now: f64,
         last_animation_frame_time: f64,
         _mouse_pos: &Pos,
-        _context: &web_sys::CanvasRenderingContext2d,
     ) {
         let frame_offset = Fps::cal_pixel_frame(self.rate, now, last_animation_frame_time);
         let is_vertical = self.direction == Direction::Vertical;
@@ -90,12 +92,12 @@ impl Behavior for WalkBehavior {
             return;
         }
 
-        match sprite.name() {
-            SpriteType::Zombie(_) => self.update_loc(&new_pos, sprite),
-            _ => (),
+        // 只有放置在草坪上的精灵随行走更新行列
+        if sprite.lane.is_some() {
+            self.update_loc(&new_pos, sprite);
         }
 
-        sprite.update_draw_info(Some(new_pos), None);
+        sprite.update_transform(Some(new_pos), None);
     }
 
     fn get_timer(&mut self) -> Option<&mut AnimationTimer> {
@@ -105,4 +107,23 @@ impl Behavior for WalkBehavior {
     fn name(&self) -> BehaviorType {
         self.name
     }
+
+    fn reuse(&mut self, data: &BehaviorData, _offset: &Pos) {
+        self.rate = data.rate;
+        self.distance = data.distance;
+    }
+
+    fn save(&self) -> BehaviorState {
+        BehaviorState::Walk {
+            offset: self.offset,
+            timer: self.timer.save(),
+        }
+    }
+
+    fn load(&mut self, state: &BehaviorState) {
+        if let BehaviorState::Walk { offset, timer } = state {
+            self.offset = *offset;
+            self.timer.load(timer);
+        }
+    }
 }
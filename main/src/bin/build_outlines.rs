@@ -0,0 +1,64 @@
+// 原生从精灵图预先计算 Hover、Click 行为（normal_shape 为 false）的轮廓，
+// 写入 assets/jsons/<sheet>-outline.json；加上 --check 时只检查已有文件是否过期
+//
+// cargo run --bin build_outlines -- [--check]
+
+use std::{env, fs, path::Path, process};
+
+use plants_vs_zombies::{headless::Headless, sprites::model::Pos};
+
+fn fail(message: String) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(1);
+}
+
+fn main() {
+    let check = env::args().any(|arg| arg == "--check");
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let manifest = Headless::manifest(&root).unwrap_or_else(|e| fail(e.to_string()));
+    let mut headless = Headless::new();
+
+    headless.load(&root).unwrap_or_else(|e| fail(e.to_string()));
+    headless.load_sheets(&root);
+
+    let sheets = headless
+        .game
+        .builder
+        .build_outlines()
+        .unwrap_or_else(|e| fail(e.to_string()));
+    let mut stale = 0;
+
+    for (sheet_name, outlines) in sheets.iter() {
+        let file_name = format!("{}-outline.json", sheet_name);
+        let path = match manifest
+            .outlines
+            .iter()
+            .find(|path| path.ends_with(&format!("/{}", file_name)))
+        {
+            Some(path) => path.to_string(),
+            None => {
+                eprintln!("warning: {} is not listed in manifest outlines", file_name);
+                format!("main/assets/jsons/{}", file_name)
+            }
+        };
+        let json = Pos::write_outlines(outlines);
+
+        if check {
+            if fs::read_to_string(root.join(&path)).ok().as_ref() != Some(&json) {
+                eprintln!("error: {} is out of date", path);
+                stale += 1;
+            }
+
+            continue;
+        }
+
+        fs::write(root.join(&path), json)
+            .unwrap_or_else(|e| fail(format!("failed to write {}: {}", path, e)));
+        println!("{} outline(s) written to {}", outlines.len(), path);
+    }
+
+    if stale > 0 {
+        eprintln!("run `cargo run --bin build_outlines` to update");
+        process::exit(1);
+    }
+}
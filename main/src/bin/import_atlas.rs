@@ -0,0 +1,49 @@
+// 原生导入 TexturePacker 导出的精灵图描述（XML 或 JSON），生成 assets/jsons 下的帧数据文件
+//
+// cargo run --bin import_atlas -- <atlas.xml|atlas.json> <sheet> [out.json]
+
+use std::{env, fs, process};
+
+use plants_vs_zombies::{atlas::Atlas, sprites::model::SpriteCell};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 3 {
+        eprintln!("usage: import_atlas <atlas.xml|atlas.json> <sheet> [out.json]");
+        process::exit(2);
+    }
+
+    let text = fs::read_to_string(&args[1]).unwrap_or_else(|e| {
+        eprintln!("error: failed to read {}: {}", args[1], e);
+        process::exit(1);
+    });
+    let atlas = Atlas::parse(&text).unwrap_or_else(|e| {
+        eprintln!("error: invalid {}: {}", args[1], e);
+        process::exit(1);
+    });
+    let cells = atlas.to_cells(&args[2]);
+    let json = Atlas::write_cells(&cells);
+
+    // 写出的文件必须能被 SpriteCell 读回
+    if let Err(e) = SpriteCell::new_from_json(&json) {
+        eprintln!("error: generated cells are invalid: {}", e);
+        process::exit(1);
+    }
+
+    match args.get(3) {
+        Some(out) => {
+            fs::write(out, json).unwrap_or_else(|e| {
+                eprintln!("error: failed to write {}: {}", out, e);
+                process::exit(1);
+            });
+            eprintln!(
+                "{} frames, {} animations written to {}",
+                atlas.frames.len(),
+                cells.len(),
+                out
+            );
+        }
+        None => print!("{}", json),
+    }
+}
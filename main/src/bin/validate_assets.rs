@@ -0,0 +1,106 @@
+// 原生校验资源清单中的 json 文件是否互相一致，有错误时逐条输出并以非零状态退出
+//
+// cargo run --bin validate_assets -- [root]
+
+use std::{env, fs, path::Path, path::PathBuf, process};
+
+use plants_vs_zombies::{
+    builder::Builder,
+    data::MANIFEST_JSON,
+    headless::Headless,
+    manifest::AssetError,
+    model::LevelData,
+    sprites::model::{Pos, SpriteCell, SpriteData},
+};
+
+// 读取并解析一个文件，出错时记录文件路径
+fn read<T>(
+    root: &Path,
+    path: &str,
+    parse: fn(&str) -> Result<T, serde_json::Error>,
+    errors: &mut Vec<AssetError>,
+) -> Option<T> {
+    let json = match fs::read_to_string(root.join(path)) {
+        Ok(json) => json,
+        Err(e) => {
+            errors.push(AssetError::Fetch {
+                path: path.to_string(),
+                reason: e.to_string(),
+            });
+
+            return None;
+        }
+    };
+
+    match parse(&json) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            errors.push(AssetError::Parse {
+                path: path.to_string(),
+                reason: e.to_string(),
+            });
+
+            None
+        }
+    }
+}
+
+fn main() {
+    let root = match env::args().nth(1) {
+        Some(root) => PathBuf::from(root),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join(".."),
+    };
+    let manifest = match Headless::manifest(&root) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("error: invalid {}: {}", MANIFEST_JSON, e);
+            process::exit(2);
+        }
+    };
+    let mut builder = Builder::new();
+    let mut levels: Vec<LevelData> = vec![];
+    let mut errors: Vec<AssetError> = vec![];
+
+    for path in manifest.cells.iter() {
+        builder
+            .cells
+            .extend(read(&root, path, SpriteCell::new_from_json, &mut errors).unwrap_or_default());
+    }
+
+    for path in manifest.data.iter() {
+        builder
+            .data
+            .extend(read(&root, path, SpriteData::new_from_json, &mut errors).unwrap_or_default());
+    }
+
+    for path in manifest.levels.iter() {
+        levels.extend(read(&root, path, LevelData::new_from_json, &mut errors).unwrap_or_default());
+    }
+
+    for path in manifest.outlines.iter() {
+        builder
+            .outlines
+            .extend(read(&root, path, Pos::outlines_from_json, &mut errors).unwrap_or_default());
+    }
+
+    // 文件解析失败时交叉引用的结果不可信
+    if errors.is_empty() {
+        errors.extend(builder.validate(&levels));
+    }
+
+    for error in errors.iter() {
+        eprintln!("error: {}", error);
+    }
+
+    if !errors.is_empty() {
+        eprintln!("{} error(s) found", errors.len());
+        process::exit(1);
+    }
+
+    println!(
+        "{} cells, {} sprite data, {} level(s) ok",
+        builder.cells.len(),
+        builder.data.len(),
+        levels.len()
+    );
+}
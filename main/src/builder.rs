@@ -1,12 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 
-use web_sys::HtmlImageElement;
-
 use crate::artists::artist::Artist;
 use crate::behavior::click::ClickBehavior;
 use crate::behavior::collision::CollisionBehavior;
-use crate::behavior::cycle::CycleBehavior;
 use crate::behavior::drag::DragBehavior;
 use crate::behavior::frequency::FrequencyBehavior;
 use crate::behavior::interval::IntervalBehavior;
@@ -15,19 +12,28 @@ use crate::behavior::scroll::ScrollBehavior;
 use crate::behavior::switch::SwitchBehavior;
 use crate::behavior::walk::WalkBehavior;
 use crate::behavior::{hover::HoverBehavior, model::Behavior};
-use crate::callback::ErasedFnPointer;
 use crate::loc::Loc;
-use crate::model::{LocInfo, SpriteType};
-use crate::sprites::model::{CollisionMargin, DrawInfo, Pos, SpriteCell, SpriteData};
-use crate::sprites::plants::plant::PlantSprite;
+use crate::manifest::AssetError;
+use crate::model::{LevelData, LocInfo, SpriteType};
+use crate::renderer::Image;
+use crate::spatial::{COLS, ROWS};
+use crate::sprites::components::{Animation, Clickable, Collider, Lane, SwitchState, Transform};
+use crate::sprites::model::{Pos, SpriteCell, SpriteData, SpriteOrigin};
 use crate::sprites::sprite::Sprite;
 use crate::sprites::sprite_wrap::SpriteWrap;
-use crate::sprites::zombies::zombie::ZombieSprite;
 
 pub struct Builder {
-    pub sheets: HashMap<String, Rc<HtmlImageElement>>,
+    // 按资源清单中的精灵图名称，如 plant
+    pub sheets: HashMap<String, Rc<dyn Image>>,
     pub cells: HashMap<String, Vec<SpriteCell>>,
     pub data: HashMap<String, SpriteData>,
+    pub outlines: HashMap<String, Vec<Pos>>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Builder {
@@ -36,24 +42,159 @@ impl Builder {
             sheets: HashMap::new(),
             cells: HashMap::new(),
             data: HashMap::new(),
+            outlines: HashMap::new(),
         }
     }
 
     pub fn get_data(&self, name: &str) -> SpriteData {
-        return match self.data.get(name) {
+        match self.data.get(name) {
             Some(sprite_data) => (*sprite_data).clone(),
             None => SpriteData::new(vec![Pos::new(0.0, 0.0)], vec![]),
-        };
+        }
     }
 
+    // 缺少的帧数据应在加载时由 validate 报告
     pub fn get_cells(&self, name: &str) -> &Vec<SpriteCell> {
-        let cells = self.cells.get(name).unwrap();
+        let cells = self
+            .cells
+            .get(name)
+            .unwrap_or_else(|| panic!("cells {} not found", name));
 
-        return cells;
+        cells
+    }
+
+    // 按精灵图分组，从第一帧计算 Hover、Click（normal_shape 为 false）的轮廓，
+    // 点相对于第一帧的左上角；需要的精灵图必须已经加载
+    pub fn build_outlines(
+        &self,
+    ) -> Result<BTreeMap<String, BTreeMap<String, Vec<Pos>>>, AssetError> {
+        let mut sheets: BTreeMap<String, BTreeMap<String, Vec<Pos>>> = BTreeMap::new();
+
+        for (name, sprite_data) in self.data.iter() {
+            let needs_outline = sprite_data.behaviors.iter().any(|behavior| {
+                let hit =
+                    behavior.name == BehaviorType::Hover || behavior.name == BehaviorType::Click;
+
+                hit && !behavior.normal_shape
+            });
+
+            if !needs_outline {
+                continue;
+            }
+
+            let sheet_name = name.split('/').next().unwrap_or_default();
+            let image = self
+                .sheets
+                .get(sheet_name)
+                .ok_or_else(|| AssetError::Image {
+                    path: sheet_name.to_string(),
+                    reason: format!("sheet of {} is not loaded", name),
+                })?;
+            let cell = self
+                .cells
+                .get(name)
+                .and_then(|cells| cells.first())
+                .ok_or_else(|| AssetError::MissingCells {
+                    key: name.to_string(),
+                    by: name.to_string(),
+                })?;
+            let points =
+                Artist::get_image_outline_points2(Some(image.as_ref()), cell, 0.0, 0.0, false, 1.0);
+
+            sheets
+                .entry(sheet_name.to_string())
+                .or_default()
+                .insert(name.to_string(), points);
+        }
+
+        Ok(sheets)
+    }
+
+    // 精灵数据与关卡引用的帧数据都要存在，关卡中的名称与行列要有效，否则创建精灵时会失败
+    pub fn validate(&self, levels: &[LevelData]) -> Vec<AssetError> {
+        let mut errors: Vec<AssetError> = vec![];
+        let require = |errors: &mut Vec<AssetError>, key: String, by: &str| {
+            if !self.cells.contains_key(&key) {
+                errors.push(AssetError::MissingCells {
+                    key,
+                    by: by.to_string(),
+                });
+            }
+        };
+        let mut names: Vec<&String> = self.data.keys().collect();
+
+        names.sort();
+
+        for name in names {
+            require(&mut errors, name.to_string(), name);
+
+            // 切换行为引用的帧数据缺失时会被静默跳过
+            for behavior in self.data[name].behaviors.iter() {
+                for switch_cell in behavior.switch_cells.iter() {
+                    require(&mut errors, switch_cell.to_string(), name);
+                }
+
+                if behavior.name == BehaviorType::Interval && behavior.interval.is_none() {
+                    errors.push(AssetError::MissingField {
+                        field: "interval".to_string(),
+                        by: name.to_string(),
+                    });
+                }
+            }
+        }
+
+        let mut outlines: Vec<&String> = self.outlines.keys().collect();
+
+        outlines.sort();
+
+        for name in outlines {
+            require(&mut errors, name.to_string(), "outlines");
+        }
+
+        for level in levels {
+            let by = format!("level {}", level.name);
+            let scenes = level.scenes.iter().map(|name| ("interface", name));
+            let cards = level.plant_cards.iter().map(|name| ("card", name));
+            let plants = level.plants.iter().map(|(name, ..)| ("plant", name));
+            let zombies = level.zombies.iter().map(|(name, ..)| ("zombie", name));
+
+            for (sheet, name) in scenes.chain(cards).chain(plants).chain(zombies) {
+                require(&mut errors, format!("{}/{}", sheet, name), &by);
+            }
+
+            // 植物、僵尸的种类只由精灵数据决定，没有数据即为未知
+            let cards = level.plant_cards.iter().map(|name| ("card", name));
+            let plants = level.plants.iter().map(|(name, ..)| ("plant", name));
+            let zombies = level.zombies.iter().map(|(name, ..)| ("zombie", name));
+
+            for (sheet, name) in cards.chain(plants).chain(zombies) {
+                if !self.data.contains_key(&format!("{}/{}", sheet, name)) {
+                    errors.push(AssetError::UnknownSprite {
+                        name: name.to_string(),
+                        by: by.to_string(),
+                    });
+                }
+            }
+
+            for (name, row, col) in level.plants.iter().chain(level.zombies.iter()) {
+                if *row >= ROWS || *col >= COLS {
+                    errors.push(AssetError::OutOfLawn {
+                        name: name.to_string(),
+                        row: *row,
+                        col: *col,
+                        by: by.to_string(),
+                    });
+                }
+            }
+        }
+
+        errors.dedup();
+
+        errors
     }
 
     pub fn get_cells_may_not_exit(&self, name: &str) -> Option<&Vec<SpriteCell>> {
-        return self.cells.get(name);
+        self.cells.get(name)
     }
 
     pub fn create_sprite(
@@ -64,9 +205,13 @@ impl Builder {
     ) -> Vec<SpriteWrap> {
         let mut sprites: Vec<SpriteWrap> = vec![];
         let cell_name = format!("{}/{}", sheet_name, name);
-        let sheet_name = format!("main/assets/images/{}", sheet_name);
         let cells = self.get_cells(&cell_name);
-        let image = self.sheets.get(&sheet_name).unwrap();
+        let image = self.sheets.get(sheet_name);
+        let outline = self.outlines.get(&cell_name);
+        let sprite_data = match sprite_data {
+            Some(sprite_data) => sprite_data,
+            None => self.get_data(&cell_name),
+        };
         let SpriteData {
             constructor,
             pos,
@@ -76,108 +221,79 @@ impl Builder {
             scale,
             order,
             collision_margin,
+            health,
+            projectile,
+            shooter,
+            sun_producer,
             ..
-        } = match sprite_data {
-            Some(sprite_data) => sprite_data,
-            None => self.get_data(&cell_name),
-        };
+        } = sprite_data.clone();
         let constructor = constructor.as_str();
-
-        let collision_margin = match constructor {
-            "ZombieSprite" => collision_margin,
-            "PlantSprite" => collision_margin,
-            _ => CollisionMargin::no_collision(),
+        let has_behavior = |behavior_type| {
+            behaviors
+                .iter()
+                .any(|behavior| behavior.name == behavior_type)
         };
 
         for item in pos {
-            let image2 = image.clone();
-            let image = image.clone();
-            let draw_info = DrawInfo::new(item, offset, visible, order);
-            let artist = Artist::new(image, cells.to_vec(), scale);
-            let sprite = Sprite::new(
-                SpriteType::from_str(name),
+            let transform = Transform::new(item, offset, visible, order);
+            let artist = Artist::new(image.cloned(), cells.to_vec(), scale);
+            let mut sprite = Sprite::new(
+                SpriteType::new(sheet_name, name),
                 Box::new(artist),
-                draw_info,
-                collision_margin,
+                transform,
             );
 
-            let mut behaviors: Vec<Box<dyn Behavior>> = behaviors
-                .iter()
-                .map(|behavior_data| {
-                    self.create_behavior(&behavior_data, image2.as_ref(), &cells[0], &item, scale)
-                })
-                .collect();
-
+            // 组件由构造器类型与数据决定
             match constructor {
-                "ZombieSprite" => {
-                    // https://stackoverflow.com/questions/38302270/why-does-the-address-of-an-object-change-across-methods
-                    // 必须先 Box 不能先给 zombie 注册回调再 Box
-                    let mut zombie = Box::new(ZombieSprite::new(sprite));
-
-                    Builder::register_zombie_callback(&mut zombie, &mut behaviors);
-
-                    sprites.push(SpriteWrap::new(zombie, behaviors));
+                "ZombieSprite" | "PlantSprite" => {
+                    sprite.health = Some(health.unwrap_or_default());
+                    sprite.collider = Some(Collider::new(collision_margin));
                 }
-                "PlantSprite" => {
-                    let mut plant = Box::new(PlantSprite::new(sprite));
-
-                    Builder::register_plant_callback(&mut plant, &mut behaviors);
+                _ => (),
+            }
 
-                    sprites.push(SpriteWrap::new(plant, behaviors));
-                }
-                _ => {
-                    sprites.push(SpriteWrap::new(Box::new(sprite), behaviors));
-                }
+            sprite.switch = match constructor {
+                "ZombieSprite" => Some(SwitchState::new(true, 0)),
+                _ if has_behavior(BehaviorType::Switch) => Some(SwitchState::new(false, 0)),
+                _ => None,
             };
-        }
-
-        return sprites;
-    }
-
-    pub fn register_plant_callback(
-        plant: &mut PlantSprite,
-        behaviors: &mut Vec<Box<dyn Behavior>>,
-    ) {
-        for behavior in behaviors {
-            match behavior.name() {
-                BehaviorType::Switch => {
-                    let pointer =
-                        ErasedFnPointer::from_associated(plant, PlantSprite::swtich_callback);
-
-                    behavior.set_cb(pointer);
-                }
-                BehaviorType::Interval => {
-                    let pointer =
-                        ErasedFnPointer::from_associated(plant, PlantSprite::interval_callback);
 
-                    behavior.set_cb(pointer);
-                }
-                _ => (),
+            if has_behavior(BehaviorType::Click) {
+                sprite.clickable = Some(Clickable::default());
             }
-        }
-    }
 
-    pub fn register_zombie_callback(
-        zombie: &mut ZombieSprite,
-        behaviors: &mut Vec<Box<dyn Behavior>>,
-    ) {
-        for behavior in behaviors {
-            match behavior.name() {
-                BehaviorType::Switch => {
-                    let pointer =
-                        ErasedFnPointer::from_associated(zombie, ZombieSprite::swtich_callback);
-
-                    behavior.set_cb(pointer);
-                }
-                BehaviorType::Collision => {
-                    let pointer =
-                        ErasedFnPointer::from_associated(zombie, ZombieSprite::collision_callback);
+            sprite.animation = behaviors
+                .iter()
+                .find(|behavior| behavior.name == BehaviorType::Cycle)
+                .map(|behavior| Animation::new(behavior.duration, behavior.interval));
+            sprite.projectile = projectile;
+            sprite.shooter = shooter.clone();
+            sprite.sun_producer = sun_producer.clone();
 
-                    behavior.set_cb(pointer);
-                }
-                _ => (),
-            }
+            let behaviors: Vec<Box<dyn Behavior>> = behaviors
+                .iter()
+                .filter_map(|behavior_data| {
+                    self.create_behavior(
+                        behavior_data,
+                        image.map(|image| image.as_ref()),
+                        &cells[0],
+                        outline,
+                        &item,
+                        scale,
+                    )
+                })
+                .collect();
+            let mut sprite_wrap = SpriteWrap::new(sprite, behaviors);
+            let data = SpriteData {
+                pos: vec![item],
+                ..sprite_data.clone()
+            };
+
+            sprite_wrap.origin = Some(SpriteOrigin::new(sheet_name, name, data));
+            sprites.push(sprite_wrap);
         }
+
+        sprites
     }
 
     pub fn from_names_and_poss(
@@ -186,11 +302,10 @@ impl Builder {
         names: Vec<&str>,
         locs: Vec<Vec<Pos>>,
     ) -> Vec<SpriteWrap> {
-        return locs
-            .into_iter()
+        locs.into_iter()
             .enumerate()
             .flat_map(|(i, pos)| self.from_name_and_pos(sheet_name, names[i], pos))
-            .collect();
+            .collect()
     }
 
     pub fn from_name_and_pos(
@@ -204,24 +319,25 @@ impl Builder {
 
         sprite_data.pos = pos;
 
-        return self.create_sprite(sheet_name, name, Some(sprite_data));
+        self.create_sprite(sheet_name, name, Some(sprite_data))
     }
 
     pub fn from_names(&self, sheet_name: &str, names: Vec<&str>) -> Vec<SpriteWrap> {
-        return names
+        names
             .iter()
             .flat_map(|name| self.create_sprite(sheet_name, name, None))
-            .collect();
+            .collect()
     }
 
     pub fn create_behavior(
         &self,
         behavior_data: &BehaviorData,
-        image: &HtmlImageElement,
+        image: Option<&dyn Image>,
         cell: &SpriteCell,
+        outline: Option<&Vec<Pos>>,
         pos: &Pos,
         scale: f64,
-    ) -> Box<dyn Behavior> {
+    ) -> Option<Box<dyn Behavior>> {
         let BehaviorData {
             name,
             duration,
@@ -234,33 +350,34 @@ impl Builder {
             direction,
             ..
         } = behavior_data;
+        // 有预先计算的轮廓时不再读取精灵图的像素
+        let outline_points = || match outline {
+            Some(outline) if !*normal_shape => {
+                Artist::place_outline(outline, pos.left, pos.top, scale)
+            }
+            _ => Artist::get_image_outline_points2(
+                image,
+                cell,
+                pos.left,
+                pos.top,
+                *normal_shape,
+                scale,
+            ),
+        };
 
-        return match name {
+        let behavior: Box<dyn Behavior> = match name {
             BehaviorType::Hover => {
-                let points = Artist::get_image_outline_points2(
-                    image,
-                    cell,
-                    pos.left,
-                    pos.top,
-                    *normal_shape,
-                    scale,
-                );
+                let points = outline_points();
 
                 Box::new(HoverBehavior::new(points))
             }
             BehaviorType::Click => {
-                let points = Artist::get_image_outline_points2(
-                    image,
-                    cell,
-                    pos.left,
-                    pos.top,
-                    *normal_shape,
-                    scale,
-                );
+                let points = outline_points();
 
                 Box::new(ClickBehavior::new(points))
             }
-            BehaviorType::Cycle => Box::new(CycleBehavior::new(*duration, *interval)),
+            // 帧动画由 Animation 组件与 systems::animate 处理
+            BehaviorType::Cycle => return None,
             BehaviorType::Walk => {
                 Box::new(WalkBehavior::new(*rate, *duration, *direction, *distance))
             }
@@ -279,8 +396,15 @@ impl Builder {
             BehaviorType::Scroll => Box::new(ScrollBehavior::new(*distance, *rate)),
             BehaviorType::Collision => Box::new(CollisionBehavior::new()),
             BehaviorType::Drag => Box::new(DragBehavior::new()),
-            BehaviorType::Interval => Box::new(IntervalBehavior::new(interval.unwrap())),
+            BehaviorType::Interval => Box::new(IntervalBehavior::new((*interval)?)),
         };
+
+        Some(behavior)
+    }
+
+    pub fn restore_sprite(&self, origin: &SpriteOrigin) -> SpriteWrap {
+        self.create_sprite(&origin.sheet, &origin.name, Some(origin.data.clone()))
+            .remove(0)
     }
 
     pub fn create_plant(&self, loc_info: &LocInfo, is_plant: bool) -> SpriteWrap {
@@ -295,15 +419,15 @@ impl Builder {
             .from_name_and_pos(sheet_name, &loc_info.0, vec![pos])
             .remove(0);
 
-        sprite_wrap.sprite.update_loc(loc_info.1, loc_info.2);
+        sprite_wrap.sprite.lane = Some(Lane::new(loc_info.1, loc_info.2));
 
-        return sprite_wrap;
+        sprite_wrap
     }
 
-    pub fn create_plants(&self, loc_infos: &Vec<LocInfo>, is_plant: bool) -> Vec<SpriteWrap> {
-        return loc_infos
-            .into_iter()
+    pub fn create_plants(&self, loc_infos: &[LocInfo], is_plant: bool) -> Vec<SpriteWrap> {
+        loc_infos
+            .iter()
             .map(|loc_info| self.create_plant(loc_info, is_plant))
-            .collect();
+            .collect()
     }
 }
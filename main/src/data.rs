@@ -4,8 +4,16 @@ pub const CANVAS_HEIGHT: u32 = 600;
 
 pub const ROW_Y_COORD: [f64; 6] = [75.0, 175.0, 270.0, 380.0, 470.0, 575.0];
 
+// 回收不可见精灵的间隔，与阳光产生间隔无关
+pub const GC_INTERVAL: f64 = 1000.0;
+
 pub const COL_X_COORD: [f64; 17] = [
     100.0, 140.0, 220.0, 295.0, 379.0, 460.0, 540.0, 625.0, 695.0, 775.0, 855.0, 935.0,
     // 定位僵尸初始位置
     1015.0, 1095.0, 1175.0, 1255.0, 1335.0,
 ];
+
+// 点击轮廓简化时允许的偏差（像素）
+pub const OUTLINE_EPSILON: f64 = 1.0;
+
+pub const MANIFEST_JSON: &str = "main/assets/manifest.json";
@@ -1,19 +1,40 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 use wasm_bindgen::{prelude::*, JsCast};
 use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlCanvasElement;
 
 use crate::{
-    asset_image, asset_json,
+    data::{CANVAS_HEIGHT, CANVAS_WIDTH, MANIFEST_JSON},
     game::Game,
+    keymap::Keymap,
     loader::Loader,
-    model::{Event, LevelData},
-    sprites::model::{SpriteCell, SpriteData},
-    util::request_animation_frame,
+    log,
+    manifest::AssetError,
+    model::LevelData,
+    pointer::PointerPhase,
+    renderer::{canvas::CanvasRenderer, Image},
+    snapshot::Snapshot,
+    sprites::model::{Pos, SpriteCell, SpriteData},
+    time_system::Speed,
+    timer::clock::PerformanceClock,
+    util::{request_animation_frame, window},
 };
 
+// 关闭页面时保存的对局，下次打开时继续
+const SNAPSHOT_KEY: &str = "plants-vs-zombies-snapshot";
+
+thread_local! {
+    // 供导出录像等从 JS 调用的接口访问当前对局
+    static GAME: RefCell<Option<Rc<RefCell<Game>>>> = const { RefCell::new(None) };
+}
+
 pub struct Engine {
     game: Rc<RefCell<Game>>,
+    canvas: HtmlCanvasElement,
 }
 
 pub enum EngineError {
@@ -39,84 +60,349 @@ impl From<EngineError> for JsValue {
 impl Engine {
     pub fn launch() {
         spawn_local(async move {
-            let loader = Loader::new(
-                vec![
-                    asset_json!("interface-cell"),
-                    asset_json!("card-cell"),
-                    asset_json!("plant-cell"),
-                    asset_json!("zombie-cell"),
-                ],
-                vec![
-                    asset_json!("interface-data"),
-                    asset_json!("card-data"),
-                    asset_json!("plant-data"),
-                    asset_json!("zombie-data"),
-                ],
-                vec![asset_json!("level-data")],
-                vec![
-                    asset_image!("interface"),
-                    asset_image!("card"),
-                    asset_image!("plant"),
-                    asset_image!("zombie"),
-                ],
-            );
+            let document = window().document().unwrap();
+            let canvas = CanvasRenderer::create_canvas(CANVAS_WIDTH, CANVAS_HEIGHT);
+            let renderer = CanvasRenderer::new(canvas.clone());
+
+            document.body().unwrap().append_child(&canvas).unwrap();
+
             let engine = Engine {
-                game: Rc::new(RefCell::new(Game::new())),
+                game: Rc::new(RefCell::new(Game::new(
+                    Box::new(renderer),
+                    Box::new(PerformanceClock),
+                ))),
+                canvas,
             };
-            let game = Rc::clone(&engine.game);
+
+            engine.fit();
+            engine.fit_on_resize();
+            engine.game.borrow_mut().start_loading(0);
+
+            // 出错时停留在加载场景显示错误
+            if !engine.load().await {
+                return;
+            }
 
             {
-                let mut game = game.borrow_mut();
+                let mut game = engine.game.borrow_mut();
+
+                // 从快照继续的对局不是从头开始，无法录制成可回放的录像
+                if !Engine::restore(&mut game) {
+                    game.init();
+                    game.start_recording();
+                }
+            }
+
+            GAME.with(|game| *game.borrow_mut() = Some(Rc::clone(&engine.game)));
 
-                loader
-                    .load_jsons(&loader.cells)
-                    .await
-                    .iter()
-                    .map(|json| SpriteCell::new_from_json(json))
-                    .for_each(|cell| game.builder.cells.extend(cell));
-                loader
-                    .load_jsons(&loader.data)
-                    .await
-                    .iter()
-                    .map(|json| SpriteData::new_from_json(json))
-                    .for_each(|data| game.builder.data.extend(data));
-
-                loader
-                    .load_jsons(&loader.level)
-                    .await
-                    .iter()
-                    .map(|json| LevelData::new_from_json(json))
-                    .for_each(|level| game.leval_data.extend(level));
-
-                game.cur_level = game.leval_data.get(0).unwrap().clone();
-                game.builder.sheets.extend(loader.load_images().await);
-                game.init();
-            }
-
-            engine.listen_event(Event::Mouseenter);
-            engine.listen_event(Event::Mouseleave);
-            engine.listen_event(Event::Mousedown);
-            engine.listen_event(Event::Mouseup);
-            engine.listen_event(Event::Mousemove);
+            engine.listen_pointer("pointerenter", PointerPhase::Enter);
+            engine.listen_pointer("pointerleave", PointerPhase::Leave);
+            engine.listen_pointer("pointerdown", PointerPhase::Down);
+            engine.listen_pointer("pointerup", PointerPhase::Up);
+            engine.listen_pointer("pointercancel", PointerPhase::Cancel);
+            engine.listen_pointer("pointermove", PointerPhase::Move);
 
+            engine.listen_keydown();
+            engine.pause_on_hidden();
+            engine.save_on_pagehide();
             engine.start_loop();
         });
     }
 
-    fn listen_event(&self, name: Event) {
+    // 按资源清单加载，进度与错误显示在加载场景中；有任何错误时返回 false
+    async fn load(&self) -> bool {
+        let loader = match Loader::from_manifest(MANIFEST_JSON).await {
+            Ok(loader) => loader,
+            Err(e) => return self.fail_loading(vec![e]),
+        };
+
+        self.game.borrow_mut().loading.start(loader.manifest.len());
+
+        let on_loaded = |path: &str, error: Option<AssetError>| {
+            let mut game = self.game.borrow_mut();
+
+            game.loading.finish(path, error);
+            game.draw_sprites(0.0);
+        };
+        let cells = loader.load_jsons(&loader.manifest.cells, &on_loaded).await;
+        let data = loader.load_jsons(&loader.manifest.data, &on_loaded).await;
+        let levels = loader.load_jsons(&loader.manifest.levels, &on_loaded).await;
+        let outlines = loader
+            .load_jsons(&loader.manifest.outlines, &on_loaded)
+            .await;
+        let images = loader.load_images(&on_loaded).await;
+        let mut errors: Vec<AssetError> = vec![];
+        let parse_error = |path: &str, e: serde_json::Error| AssetError::Parse {
+            path: path.to_string(),
+            reason: e.to_string(),
+        };
+
+        {
+            let mut game = self.game.borrow_mut();
+
+            for (path, json) in cells.iter() {
+                match SpriteCell::new_from_json(json) {
+                    Ok(cells) => game.builder.cells.extend(cells),
+                    Err(e) => errors.push(parse_error(path, e)),
+                }
+            }
+
+            for (path, json) in data.iter() {
+                match SpriteData::new_from_json(json) {
+                    Ok(data) => game.builder.data.extend(data),
+                    Err(e) => errors.push(parse_error(path, e)),
+                }
+            }
+
+            for (path, json) in levels.iter() {
+                match LevelData::new_from_json(json) {
+                    Ok(levels) => game.leval_data.extend(levels),
+                    Err(e) => errors.push(parse_error(path, e)),
+                }
+            }
+
+            for (path, json) in outlines.iter() {
+                match Pos::outlines_from_json(json) {
+                    Ok(outlines) => game.builder.outlines.extend(outlines),
+                    Err(e) => errors.push(parse_error(path, e)),
+                }
+            }
+
+            game.builder.sheets.extend(
+                images
+                    .into_iter()
+                    .map(|(name, image)| (name, image as Rc<dyn Image>)),
+            );
+            errors.extend(game.builder.validate(&game.leval_data));
+
+            match game.leval_data.first().cloned() {
+                Some(level) => game.cur_level = level,
+                None => errors.push(AssetError::Parse {
+                    path: MANIFEST_JSON.to_string(),
+                    reason: String::from("no level"),
+                }),
+            }
+        }
+
+        return match errors.is_empty() && !self.game.borrow().loading.is_failed() {
+            true => true,
+            false => self.fail_loading(errors),
+        };
+    }
+
+    fn fail_loading(&self, errors: Vec<AssetError>) -> bool {
+        let mut game = self.game.borrow_mut();
+
+        errors.into_iter().for_each(|e| game.loading.fail(e));
+        game.loading.errors.iter().for_each(|e| log!("{}", e));
+        game.draw_sprites(0.0);
+
+        false
+    }
+
+    fn restore(game: &mut Game) -> bool {
+        let storage = match window().local_storage() {
+            Ok(Some(storage)) => storage,
+            _ => return false,
+        };
+        let json = match storage.get_item(SNAPSHOT_KEY) {
+            Ok(Some(json)) => json,
+            _ => return false,
+        };
+
+        // 快照只用一次，恢复失败也不再重试
+        let _ = storage.remove_item(SNAPSHOT_KEY);
+
+        match Snapshot::new_from_json(&json).and_then(|snapshot| game.load_snapshot(&snapshot)) {
+            Ok(()) => true,
+            Err(e) => {
+                log!("{}", e);
+
+                false
+            }
+        }
+    }
+
+    // 关闭页面时只保存进行中的战斗
+    fn save_on_pagehide(&self) {
+        let game = Rc::clone(&self.game);
+        let closure = Closure::wrap(Box::new(move || {
+            let game = game.borrow();
+            let storage = match window().local_storage() {
+                Ok(Some(storage)) => storage,
+                _ => return,
+            };
+
+            // 只保存进行中的战斗，其他场景清除上次的快照
+            let _ = match game.in_battle() {
+                true => storage.set_item(SNAPSHOT_KEY, &game.save_snapshot().to_json()),
+                false => storage.remove_item(SNAPSHOT_KEY),
+            };
+        }) as Box<dyn FnMut()>);
+
+        window()
+            .add_event_listener_with_callback("pagehide", closure.as_ref().unchecked_ref())
+            .unwrap();
+
+        closure.forget();
+    }
+
+    // 页面不可见时自动暂停；只恢复自动暂停的对局，手动暂停保持不变
+    fn pause_on_hidden(&self) {
         let game = Rc::clone(&self.game);
-        let game2 = Rc::clone(&self.game);
-        let closure = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
-            game.borrow_mut().dispatch_event(name, event);
+        let auto_paused = Rc::new(Cell::new(false));
+        let closure = Closure::wrap(Box::new(move || {
+            let mut game = game.borrow_mut();
+            let hidden = window().document().unwrap().hidden();
+
+            if hidden && !game.is_paused() {
+                game.pause();
+                auto_paused.set(true);
+            } else if !hidden && auto_paused.get() {
+                game.resume();
+                auto_paused.set(false);
+            }
+        }) as Box<dyn FnMut()>);
+
+        window()
+            .document()
+            .unwrap()
+            .add_event_listener_with_callback("visibilitychange", closure.as_ref().unchecked_ref())
+            .unwrap();
+
+        closure.forget();
+    }
+
+    pub fn set_paused(paused: bool) {
+        GAME.with(|game| {
+            if let Some(game) = game.borrow().as_ref() {
+                let mut game = game.borrow_mut();
+
+                match paused {
+                    true => game.pause(),
+                    false => game.resume(),
+                }
+            }
+        });
+    }
+
+    pub fn set_speed(speed: Speed) -> bool {
+        GAME.with(|game| match game.borrow().as_ref() {
+            Some(game) => {
+                game.borrow_mut().set_speed(speed);
+                true
+            }
+            None => false,
+        })
+    }
+
+    pub fn slow_motion(duration: f64) -> bool {
+        GAME.with(|game| match game.borrow().as_ref() {
+            Some(game) => {
+                game.borrow_mut()
+                    .set_temporary_transducer(Speed::Half.transducer(), duration);
+                true
+            }
+            None => false,
+        })
+    }
+
+    pub fn set_keymap(json: &str) -> bool {
+        let keymap = match Keymap::new_from_json(json) {
+            Ok(keymap) => keymap,
+            Err(e) => {
+                log!("invalid keymap: {}", e);
+
+                return false;
+            }
+        };
+
+        GAME.with(|game| match game.borrow().as_ref() {
+            Some(game) => {
+                game.borrow_mut().keymap = keymap;
+                true
+            }
+            None => false,
+        })
+    }
+
+    pub fn export_replay() -> Option<String> {
+        GAME.with(|game| {
+            game.borrow()
+                .as_ref()
+                .and_then(|game| game.borrow().export_replay())
+                .map(|replay| replay.to_json())
+        })
+    }
+
+    // 画布铺满父元素，按设备像素比设置实际分辨率
+    fn fit(&self) {
+        Engine::fit_game(&self.canvas, &mut self.game.borrow_mut());
+    }
+
+    fn fit_game(canvas: &HtmlCanvasElement, game: &mut Game) {
+        let (width, height) = match canvas.parent_element() {
+            Some(parent) => (parent.client_width() as f64, parent.client_height() as f64),
+            None => (CANVAS_WIDTH as f64, CANVAS_HEIGHT as f64),
+        };
+
+        game.resize(width, height, window().device_pixel_ratio());
+    }
+
+    // 浏览器缩放改变设备像素比时同样触发 resize
+    fn fit_on_resize(&self) {
+        let game = Rc::clone(&self.game);
+        let canvas = self.canvas.clone();
+        let closure = Closure::wrap(Box::new(move || {
+            Engine::fit_game(&canvas, &mut game.borrow_mut());
+        }) as Box<dyn FnMut()>);
+
+        window()
+            .add_event_listener_with_callback("resize", closure.as_ref().unchecked_ref())
+            .unwrap();
+
+        closure.forget();
+    }
+
+    // 鼠标与触摸都走指针事件，按下后捕获指针，拖出画布也能收到抬起
+    fn listen_pointer(&self, name: &str, phase: PointerPhase) {
+        let game = Rc::clone(&self.game);
+        let canvas = self.canvas.clone();
+        let closure = Closure::wrap(Box::new(move |event: web_sys::PointerEvent| {
+            let id = event.pointer_id();
+            let x = event.offset_x() as f64;
+            let y = event.offset_y() as f64;
+
+            if phase == PointerPhase::Down {
+                let _ = canvas.set_pointer_capture(id);
+            }
+
+            game.borrow_mut().dispatch_pointer(phase, id, x, y);
+        }) as Box<dyn FnMut(_)>);
+
+        self.canvas
+            .add_event_listener_with_callback(name, closure.as_ref().unchecked_ref())
+            .unwrap();
+
+        closure.forget();
+    }
+
+    // 画布默认拿不到焦点，按键在 window 上监听
+    fn listen_keydown(&self) {
+        let game = Rc::clone(&self.game);
+        let closure = Closure::wrap(Box::new(move |event: web_sys::KeyboardEvent| {
+            if event.repeat() {
+                return;
+            }
+
+            // 已绑定的按键不再触发页面滚动等默认行为
+            if game.borrow_mut().dispatch_key(&event.key()) {
+                event.prevent_default();
+            }
         }) as Box<dyn FnMut(_)>);
 
-        game2
-            .borrow()
-            .canvas
-            .add_event_listener_with_callback(
-                &name.to_string().to_lowercase(),
-                closure.as_ref().unchecked_ref(),
-            )
+        window()
+            .add_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref())
             .unwrap();
 
         closure.forget();
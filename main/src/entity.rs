@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+/* Entity */
+// 世代句柄：下标会被复用，世代号区分先后占用同一下标的精灵
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Entity {
+    pub index: usize,
+    pub generation: u32,
+}
+
+impl Entity {
+    pub fn new(index: usize, generation: u32) -> Entity {
+        Entity { index, generation }
+    }
+}
+
+/* Entities */
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Entities {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free: Vec<usize>,
+}
+
+impl Entities {
+    pub fn new() -> Entities {
+        Default::default()
+    }
+
+    pub fn create(&mut self) -> Entity {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.generations.push(0);
+                self.alive.push(false);
+
+                self.generations.len() - 1
+            }
+        };
+
+        self.alive[index] = true;
+
+        Entity::new(index, self.generations[index])
+    }
+
+    // 销毁后世代号加一，旧句柄随之失效
+    pub fn destroy(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+
+        self.alive[entity.index] = false;
+        self.generations[entity.index] += 1;
+        self.free.push(entity.index);
+
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        match self.generations.get(entity.index) {
+            Some(generation) => *generation == entity.generation && self.alive[entity.index],
+            None => false,
+        }
+    }
+}
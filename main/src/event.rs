@@ -0,0 +1,73 @@
+use std::mem;
+
+use crate::{behavior::model::BehaviorType, entity::Entity};
+
+// 行为与碰撞检测产生的事件，在逻辑帧末尾由 Game 统一处理
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameEvent {
+    Clicked(Entity),
+    AnimationFinished(Entity),
+    ScrollFinished(Entity),
+    SwitchFinished(Entity),
+    IntervalElapsed(Entity),
+    Collided(Entity, Entity),
+}
+
+impl GameEvent {
+    // 行为触发时对应的事件，碰撞事件由 Game::check_collision 产生
+    pub fn from_behavior(behavior_type: BehaviorType, entity: Entity) -> Option<GameEvent> {
+        match behavior_type {
+            BehaviorType::Click => Some(GameEvent::Clicked(entity)),
+            BehaviorType::Frequency => Some(GameEvent::AnimationFinished(entity)),
+            BehaviorType::Scroll => Some(GameEvent::ScrollFinished(entity)),
+            BehaviorType::Switch => Some(GameEvent::SwitchFinished(entity)),
+            BehaviorType::Interval => Some(GameEvent::IntervalElapsed(entity)),
+            _ => None,
+        }
+    }
+
+    pub fn entity(&self) -> Entity {
+        match *self {
+            GameEvent::Clicked(entity)
+            | GameEvent::AnimationFinished(entity)
+            | GameEvent::ScrollFinished(entity)
+            | GameEvent::SwitchFinished(entity)
+            | GameEvent::IntervalElapsed(entity)
+            | GameEvent::Collided(entity, _) => entity,
+        }
+    }
+
+    pub fn behavior_type(&self) -> BehaviorType {
+        match self {
+            GameEvent::Clicked(_) => BehaviorType::Click,
+            GameEvent::AnimationFinished(_) => BehaviorType::Frequency,
+            GameEvent::ScrollFinished(_) => BehaviorType::Scroll,
+            GameEvent::SwitchFinished(_) => BehaviorType::Switch,
+            GameEvent::IntervalElapsed(_) => BehaviorType::Interval,
+            GameEvent::Collided(_, _) => BehaviorType::Collision,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct EventQueue {
+    events: Vec<GameEvent>,
+}
+
+impl EventQueue {
+    pub fn new() -> EventQueue {
+        Default::default()
+    }
+
+    pub fn push(&mut self, event: GameEvent) {
+        self.events.push(event);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn drain(&mut self) -> Vec<GameEvent> {
+        mem::take(&mut self.events)
+    }
+}
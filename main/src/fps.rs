@@ -5,6 +5,7 @@ pub struct Fps {
     current_time: f64,
     value: f64,
     display: u16,
+    last_frame_time: f64,
     pub last_animation_frame_time: f64,
     pub last_fps_update_time: f64,
 }
@@ -18,29 +19,29 @@ impl Fps {
         }
     }
     pub fn format(value: f64) -> u16 {
-        return value as u16;
+        value as u16
     }
 
     pub fn cal_pixel_frame(rate: f64, now: f64, last_animation_frame_time: f64) -> f64 {
-        return rate * ((now - last_animation_frame_time) / 1000.0);
+        rate * ((now - last_animation_frame_time) / 1000.0)
     }
 
-    pub fn calc(&mut self, now: f64, rate: f64) {
+    // 按渲染帧统计帧率，now 为真实时间，不受游戏速度影响
+    pub fn calc(&mut self, now: f64) {
         self.current_time = now;
-        self.value = (1.0 / (now - self.last_animation_frame_time)) * 1000.0 * rate;
+        self.value = (1.0 / (now - self.last_frame_time)) * 1000.0;
+        self.last_frame_time = now;
 
         if now - self.last_fps_update_time > 1000.0 {
             self.last_fps_update_time = now;
             self.display = Fps::format(self.value);
 
-            unsafe {
-                log!("{} fps", &self.display);
-            }
+            log!("{} fps", &self.display);
         }
     }
 
     pub fn _one_frame_passed(&self, last_time: f64, animation_rate: f64) -> bool {
-        return self.current_time - last_time > animation_rate;
+        self.current_time - last_time > animation_rate
     }
 
     pub fn _increase_update(&mut self, delta: f64) {
@@ -1,41 +1,76 @@
 use std::cell::Cell;
+use std::collections::HashMap;
+use std::mem;
 use std::rc::Rc;
 
-use wasm_bindgen::JsCast;
-use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, MouseEvent};
-
 use crate::behavior::model::BehaviorType;
 use crate::builder::Builder;
-use crate::callback::ErasedFnPointer;
-use crate::data::{CANVAS_HEIGHT, CANVAS_WIDTH};
+use crate::data::{COL_X_COORD, GC_INTERVAL};
+use crate::entity::{Entities, Entity};
+use crate::event::{EventQueue, GameEvent};
+use crate::keymap::{Action, Keymap};
 use crate::loc::Loc;
 use crate::log;
-use crate::model::{Callback, Event, Interface, LevelData, Plant, SpriteType, State};
+use crate::model::{Callback, Event, Interface, LevelData, SpriteType, State};
+use crate::pointer::{PointerPhase, Pointers};
+use crate::pool::SpritePool;
+use crate::renderer::Renderer;
+use crate::replay::{InputRecord, Replay};
+use crate::rng::Rng;
 use crate::scene::home::HomeScene;
 use crate::scene::level::LevelScene;
+use crate::scene::loading::LoadProgress;
+use crate::scene::model::{SceneKind, Transition};
+use crate::snapshot::{Snapshot, SnapshotError};
+use crate::spatial::{Layer, SpatialIndex};
+use crate::sprites::components::Clickable;
 use crate::sprites::guideline::Guideline;
-use crate::sprites::model::{Pos, Update};
-use crate::sprites::plants::plant::PlantSprite;
+use crate::sprites::layer::{LayerCache, RenderLayer};
+use crate::sprites::model::Pos;
+use crate::sprites::sprite::Sprite;
 use crate::sprites::sprite_wrap::SpriteWrap;
-use crate::util::window;
-use crate::{fps::Fps, time_system::TimeSystem};
+use crate::systems;
+use crate::timer::clock::Clock;
+use crate::util::random_seed;
+use crate::viewport::Viewport;
+use crate::{
+    fps::Fps,
+    time_system::{Speed, TimeSystem, Transducer, TICK_DURATION},
+};
 
 pub struct Game {
     pub time_system: TimeSystem,
     pub fps: Fps,
-    pub time_rate: f64,
+    pub speed: Speed,
 
     pub builder: Builder,
 
-    pub canvas: HtmlCanvasElement,
-    pub context: CanvasRenderingContext2d,
+    pub renderer: Box<dyn Renderer>,
+    pub viewport: Viewport,
 
     pub enter: Rc<Cell<bool>>,
     pub pressed: Rc<Cell<bool>>,
     pub dragging: Rc<Cell<bool>>,
     pub cur_pos: Rc<Cell<Pos>>,
+    pub pointers: Pointers,
 
     pub sprites: Vec<SpriteWrap>,
+    pub entities: Entities,
+    pub lookup: HashMap<Entity, usize>,
+    pub spatial_index: SpatialIndex,
+    pub background_cache: LayerCache,
+    pub loading: LoadProgress,
+    pub pool: SpritePool,
+    pub seed_chooser: Vec<Entity>,
+    pub plant_cards: Vec<Entity>,
+    // 正在拖动的精灵及其拿起前的位置，取消拖动时放回
+    pub held: Option<(Entity, Pos)>,
+    pub events: EventQueue,
+
+    pub rng: Rng,
+    pub seed: Option<u64>,
+    pub recorder: Option<Replay>,
+    pub keymap: Keymap,
 
     pub now: f64,
 
@@ -44,6 +79,12 @@ pub struct Game {
     pub cur_level: LevelData,
 
     pub last_gc_time: f64,
+    pub last_sun_time: f64,
+
+    pub scenes: Vec<SceneKind>,
+    pub transitions: Vec<Transition>,
+    // 默认从主页开始，设为 SceneKind::Battle 时跳过主页与选卡
+    pub first_scene: SceneKind,
 
     pub state: State,
 
@@ -52,36 +93,51 @@ pub struct Game {
 }
 
 impl Game {
-    pub fn new() -> Game {
-        let document = window().document().unwrap();
-        let canvas = Game::create_canvas(CANVAS_WIDTH, CANVAS_HEIGHT);
-        let context = Game::get_canvas_context(&canvas);
-
-        document.body().unwrap().append_child(&canvas).unwrap();
-
+    pub fn new(renderer: Box<dyn Renderer>, clock: Box<dyn Clock>) -> Game {
         Game {
             now: 0.0,
-            time_system: TimeSystem::new(),
+            time_system: TimeSystem::new(clock),
             fps: Fps::new(),
-            time_rate: 1.0,
+            speed: Speed::Normal,
 
             builder: Builder::new(),
 
-            canvas,
-            context,
+            renderer,
+            viewport: Viewport::default(),
 
             enter: Rc::new(Cell::new(false)),
             cur_pos: Rc::new(Cell::new(Pos::new(0.0, 0.0))),
             pressed: Rc::new(Cell::new(false)),
             dragging: Rc::new(Cell::new(false)),
+            pointers: Pointers::new(),
 
             sprites: vec![],
+            entities: Entities::new(),
+            lookup: HashMap::new(),
+            spatial_index: SpatialIndex::new(),
+            background_cache: LayerCache::new(),
+            loading: LoadProgress::new(),
+            pool: SpritePool::new(),
+            seed_chooser: vec![],
+            plant_cards: vec![],
+            held: None,
+            events: EventQueue::new(),
+
+            rng: Rng::default(),
+            seed: None,
+            recorder: None,
+            keymap: Keymap::new(),
 
             leval_data: vec![],
 
             cur_level: LevelData::new_default(),
 
             last_gc_time: 0.0,
+            last_sun_time: 0.0,
+
+            scenes: vec![],
+            transitions: vec![],
+            first_scene: SceneKind::Home,
 
             state: State::new(),
 
@@ -90,40 +146,131 @@ impl Game {
         }
     }
 
-    pub fn create_canvas(width: u32, height: u32) -> HtmlCanvasElement {
-        let document = window().document().unwrap();
-        let canvas = document
-            .create_element("canvas")
-            .unwrap()
-            .dyn_into::<HtmlCanvasElement>()
-            .unwrap();
+    // 指定后优先于关卡自带的种子，在 init 之前调用
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    // 资源加载期间显示进度，加载完成后由 init 切换到第一个场景
+    pub fn start_loading(&mut self, total: usize) {
+        self.loading.start(total);
+        self.transition(Transition::Replace(SceneKind::Loading));
+        self.apply_transitions();
+        self.draw_sprites(0.0);
+    }
+
+    pub fn init(&mut self) {
+        let seed = self
+            .seed
+            .or(self.cur_level.seed)
+            .unwrap_or_else(random_seed);
 
-        canvas.set_width(width);
-        canvas.set_height(height);
+        log!("关卡 {} 随机种子：{}", self.cur_level.name, seed);
 
-        return canvas;
+        self.rng = Rng::new(seed);
+        self.time_system.start();
+        self.transition(Transition::Replace(self.first_scene));
+        self.apply_transitions();
     }
 
-    pub fn get_canvas_context(canvas: &HtmlCanvasElement) -> CanvasRenderingContext2d {
-        return canvas
-            .get_context("2d")
-            .unwrap()
-            .unwrap()
-            .dyn_into::<CanvasRenderingContext2d>()
-            .unwrap();
+    pub fn save_snapshot(&self) -> Snapshot {
+        Snapshot {
+            level: self.cur_level.name.clone(),
+            rng: self.rng.clone(),
+            now: self.now,
+            ticks: self.time_system.ticks,
+            last_gc_time: self.last_gc_time,
+            last_sun_time: self.last_sun_time,
+            state: self.state.clone(),
+            speed: self.speed,
+            scenes: self.scenes.clone(),
+            sun: self.sun,
+            sun_produce_rate: self.sun_produce_rate,
+            entities: self.entities.clone(),
+            seed_chooser: self.seed_chooser.clone(),
+            plant_cards: self.plant_cards.clone(),
+            sprites: self.sprites.iter().map(|sprite| sprite.save()).collect(),
+        }
     }
 
-    pub fn init(&mut self) {
+    // 代替 init 在新的 Game 上恢复，需先加载精灵数据与关卡
+    pub fn load_snapshot(&mut self, snapshot: &Snapshot) -> Result<(), SnapshotError> {
+        self.cur_level = self
+            .leval_data
+            .iter()
+            .find(|level| level.name == snapshot.level)
+            .cloned()
+            .ok_or_else(|| SnapshotError::UnknownLevel(snapshot.level.clone()))?;
+
+        self.rng = snapshot.rng.clone();
+        self.seed = Some(self.rng.seed());
+        self.now = snapshot.now;
+        self.time_system.tick_time = snapshot.now;
+        self.time_system.ticks = snapshot.ticks;
+        self.fps.last_animation_frame_time = snapshot.now;
+        self.last_gc_time = snapshot.last_gc_time;
+        self.last_sun_time = snapshot.last_sun_time;
+        self.state = snapshot.state.clone();
+        self.scenes = snapshot.scenes.clone();
+        self.sun = snapshot.sun;
+        self.sun_produce_rate = snapshot.sun_produce_rate;
+        self.entities = snapshot.entities.clone();
+        self.seed_chooser = snapshot.seed_chooser.clone();
+        self.plant_cards = snapshot.plant_cards.clone();
+        self.background_cache.invalidate();
+        self.sprites = snapshot
+            .sprites
+            .iter()
+            .map(|sprite_snapshot| {
+                let mut sprite = match &sprite_snapshot.origin {
+                    Some(origin) => self.builder.restore_sprite(origin),
+                    None => Guideline::new(),
+                };
+
+                sprite.load(sprite_snapshot);
+
+                sprite
+            })
+            .collect();
+
+        self.reindex(0);
         self.time_system.start();
-        // self.create_home_scene();
-        self.debug_start();
+        self.set_speed(snapshot.speed);
+
+        // 行为计时器的暂停状态已随快照恢复
+        if self.is_paused() {
+            self.time_system.pause();
+        }
+
+        Ok(())
     }
 
-    fn debug_start(&mut self) {
-        self.state.selected_card = true;
-        self.create_level_background();
-        self.choose_plant_card();
-        self.create_plant_card_left();
+    // 在 init 之后调用，录制从当前逻辑帧开始的输入
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(Replay::new(
+            &self.cur_level.name,
+            self.rng.seed(),
+            self.first_scene,
+        ));
+    }
+
+    pub fn stop_recording(&mut self) -> Option<Replay> {
+        let replay = self.export_replay();
+
+        self.recorder = None;
+
+        replay
+    }
+
+    // 录制中途导出，不影响继续录制
+    pub fn export_replay(&self) -> Option<Replay> {
+        self.recorder.as_ref().map(|recorder| {
+            let mut replay = recorder.clone();
+
+            replay.ticks = self.time_system.ticks;
+
+            replay
+        })
     }
 
     fn sunback(&mut self) {
@@ -132,45 +279,93 @@ impl Game {
 
         sunback.sprite.toggle();
 
-        self.sprites.push(sunback);
+        self.add_sprite(sunback);
         self.toggle_sunback();
     }
 
+    fn add_sprite(&mut self, mut sprite: SpriteWrap) -> Entity {
+        let entity = self.entities.create();
+
+        sprite.entity = entity;
+
+        self.lookup.insert(entity, self.sprites.len());
+        self.sprites.push(sprite);
+
+        entity
+    }
+
+    fn add_sprites(&mut self, sprites: Vec<SpriteWrap>) -> Vec<Entity> {
+        sprites
+            .into_iter()
+            .map(|sprite| self.add_sprite(sprite))
+            .collect()
+    }
+
+    pub fn get_sprite(&self, entity: Entity) -> Option<&SpriteWrap> {
+        self.lookup.get(&entity).map(|index| &self.sprites[*index])
+    }
+
+    pub fn get_sprite_mut(&mut self, entity: Entity) -> Option<&mut SpriteWrap> {
+        match self.lookup.get(&entity) {
+            Some(index) => Some(&mut self.sprites[*index]),
+            None => None,
+        }
+    }
+
     fn find_sprite(&mut self, sprite_type: SpriteType) -> Option<&mut SpriteWrap> {
-        return self
-            .sprites
+        self.sprites
             .iter_mut()
-            .find(|sprite_wrap| sprite_wrap.sprite.name() == sprite_type);
+            .find(|sprite_wrap| sprite_wrap.sprite.name == sprite_type)
     }
 
-    fn remove_sprites(&mut self, sprite_types: &Vec<SpriteType>) {
-        sprite_types.iter().for_each(|sprite_type| {
-            let index = self
-                .sprites
-                .iter()
-                .position(|sprite| sprite.name() == *sprite_type);
+    // 句柄已失效（精灵已被移除）时返回 None
+    pub fn remove_sprite(&mut self, entity: Entity) -> Option<SpriteWrap> {
+        let index = self.lookup.remove(&entity)?;
+        let sprite = self.sprites.remove(index);
 
-            if let Some(index) = index {
-                self.sprites.remove(index);
-            }
-        });
+        self.entities.destroy(entity);
+        self.reindex(index);
+
+        Some(sprite)
     }
 
-    fn remove_unvisible_sprites(&mut self) {
-        let mut remove_indexs: Vec<usize> = self
-            .sprites
-            .iter()
-            .enumerate()
-            .filter_map(|(index, sprite)| match sprite.is_visible() {
-                true => None,
-                false => Some(index),
-            })
-            .collect();
+    fn remove_sprites(&mut self, entities: &Vec<Entity>) {
+        for entity in entities {
+            self.remove_sprite(*entity);
+        }
+    }
 
-        remove_indexs.reverse();
-        remove_indexs.iter().for_each(|index| {
-            self.sprites.remove(*index);
-        });
+    // 移除的子弹、阳光放回对象池
+    fn retain_sprites<F: Fn(&SpriteWrap) -> bool>(&mut self, f: F) {
+        let (kept, removed): (Vec<SpriteWrap>, Vec<SpriteWrap>) =
+            mem::take(&mut self.sprites).into_iter().partition(f);
+
+        for sprite in removed {
+            self.entities.destroy(sprite.entity);
+            self.pool.release(sprite);
+        }
+
+        self.sprites = kept;
+        self.reindex(0);
+    }
+
+    pub fn clear_sprites(&mut self) {
+        self.retain_sprites(|_| false);
+    }
+
+    // sprites 顺序变化后，从 start 开始更新句柄到下标的映射
+    fn reindex(&mut self, start: usize) {
+        if start == 0 {
+            self.lookup.clear();
+        }
+
+        for (index, sprite) in self.sprites.iter().enumerate().skip(start) {
+            self.lookup.insert(sprite.entity, index);
+        }
+    }
+
+    fn remove_unvisible_sprites(&mut self) {
+        self.retain_sprites(|sprite| sprite.is_visible());
     }
 
     fn shovel_back(&mut self) -> bool {
@@ -181,11 +376,8 @@ impl Game {
         if let Some(shovel) = shovel {
             let shovel_original_pos = shovel_data.pos[0];
 
-            shovel_move = match shovel.get_draw_info() {
-                Some(draw_info) => draw_info.pos.top != 0.0,
-                None => false,
-            };
-            shovel.update_draw_info(Some(shovel_original_pos), None);
+            shovel_move = shovel.get_transform().pos.top != 0.0;
+            shovel.update_transform(Some(shovel_original_pos), None);
         }
 
         shovel_move
@@ -193,32 +385,48 @@ impl Game {
 
     fn shovel_plant(&mut self) {
         let cur_pos_loc = Loc::get_row_col_by_pos(&self.cur_pos.get());
-        let remove_plant_index = self
+        let plant = self
             .sprites
             .iter()
-            .position(|sprite| sprite.is_plant() && cur_pos_loc == sprite.get_loc());
+            .find(|sprite| sprite.is_plant() && cur_pos_loc == sprite.get_loc())
+            .map(|sprite| sprite.entity);
 
-        if let Some(remove_plant_index) = remove_plant_index {
-            self.sprites.remove(remove_plant_index);
+        if let Some(plant) = plant {
+            self.remove_sprite(plant);
         }
     }
 
-    fn schedule_draw_order(&mut self) {
-        self.sprites
-            .sort_by(|a, b| a.get_order().cmp(&b.get_order()))
-    }
-
-    pub fn dispatch_event(&mut self, name: Event, event: MouseEvent) {
+    pub fn dispatch_event(&mut self, name: Event, x: f64, y: f64) {
         let pressed = self.pressed.clone();
         let dragging = self.dragging.clone();
         let cur_pos = self.cur_pos.clone();
-        let x = event.offset_x() as f64;
-        let y = event.offset_y() as f64;
 
         cur_pos.set(Pos::new(x, y));
 
-        unsafe {
-            log!("{} : {}, {}", name, x, y);
+        // 暂停时不响应操作，点击任意位置继续；这些输入不进入录像
+        if self.is_paused() {
+            if name == Event::Mouseup {
+                pressed.set(false);
+                dragging.set(false);
+                self.resume();
+            }
+
+            return;
+        }
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(InputRecord::new(self.time_system.ticks, name, x, y));
+        }
+
+        // 结算后点击任意位置回到主页
+        if self.in_result() {
+            if name == Event::Mouseup {
+                pressed.set(false);
+                dragging.set(false);
+                self.back_home();
+            }
+
+            return;
         }
 
         match name {
@@ -228,13 +436,17 @@ impl Game {
             }
 
             Event::Mouseup => {
-                if self.state.selected_card && pressed.get() && dragging.get() && self.shovel_back()
+                if self.in_scene(SceneKind::Battle)
+                    && pressed.get()
+                    && (dragging.get() || self.held.is_some())
+                    && self.shovel_back()
                 {
                     self.shovel_plant();
                 }
 
                 pressed.set(false);
                 dragging.set(false);
+                self.held = None;
 
                 self.mouseup_handler(x, y);
             }
@@ -252,7 +464,8 @@ impl Game {
             Event::Mousemove => {
                 if pressed.get() {
                     if !dragging.get() {
-                        self.toggle_behaviors(&vec![BehaviorType::Drag], true);
+                        self.toggle_behaviors(&[BehaviorType::Drag], true);
+                        self.hold_clicked();
                     }
 
                     dragging.set(true);
@@ -260,40 +473,166 @@ impl Game {
 
                 self.mousemove_handler(x, y);
             }
+
+            Event::Keydown => (),
         };
     }
 
+    // 容器尺寸为 CSS 像素，游戏区域按比例缩放后居中
+    pub fn resize(&mut self, width: f64, height: f64, pixel_ratio: f64) {
+        self.viewport = Viewport::new(width, height, pixel_ratio);
+        self.renderer.resize(&self.viewport);
+        self.background_cache.invalidate();
+    }
+
+    // 指针事件的入口：x、y 为画布元素上的 CSS 像素，换算成逻辑坐标；
+    // 只有驱动拖动的指针转成鼠标事件，被系统中断时取消拖动
+    pub fn dispatch_pointer(&mut self, phase: PointerPhase, id: i32, x: f64, y: f64) {
+        let Pos { left: x, top: y } = self.viewport.to_logical(x, y);
+        let canceled = phase == PointerPhase::Cancel && self.pointers.active() == Some(id);
+
+        if let Some(event) = self.pointers.translate(phase, id) {
+            self.dispatch_event(event, x, y);
+        }
+
+        if canceled {
+            self.dispatch_action(Action::CancelDrag);
+        }
+    }
+
+    // 返回按键是否绑定了操作，未绑定的按键交还给浏览器
+    pub fn dispatch_key(&mut self, key: &str) -> bool {
+        match self.keymap.get(key) {
+            Some(action) => {
+                self.dispatch_action(action);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn dispatch_action(&mut self, action: Action) {
+        // 暂停与继续不改变对局，不进入录像
+        match (action, self.is_paused()) {
+            (Action::Pause, true) => return self.resume(),
+            (Action::Pause, false) => return self.pause(),
+            (_, true) => return,
+            _ => (),
+        }
+
+        let cur_pos = self.cur_pos.get();
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(InputRecord::new_action(
+                self.time_system.ticks,
+                action,
+                cur_pos.left,
+                cur_pos.top,
+            ));
+        }
+
+        match action {
+            Action::SelectSeed(index) => {
+                if let Some(card) = self.plant_cards.get(index).copied() {
+                    self.pick_up(card);
+                }
+            }
+            Action::Shovel => {
+                let shovel = self
+                    .find_sprite(SpriteType::Interface(Interface::Shovel))
+                    .map(|shovel| shovel.entity);
+
+                if let Some(shovel) = shovel {
+                    self.pick_up(shovel);
+                }
+            }
+            Action::CancelDrag => self.cancel_drag(),
+            Action::ToggleGuideline => self.state.guideline = !self.state.guideline,
+            Action::CycleSpeed => self.set_speed(self.speed.next()),
+            Action::Pause => (),
+        }
+    }
+
+    // 按键拿起：精灵移到指针处并跟随移动，点击放下
+    fn pick_up(&mut self, entity: Entity) {
+        let now = self.now;
+        let cur_pos = self.cur_pos.get();
+
+        self.cancel_drag();
+
+        if let Some(sprite) = self.get_sprite_mut(entity) {
+            if sprite.sprite.clickable.is_none() || !sprite.has_behavior(BehaviorType::Drag) {
+                return;
+            }
+
+            let origin = sprite.get_transform().pos;
+
+            sprite.sprite.clickable = Some(Clickable { clicked: true });
+            sprite.update_transform(Some(cur_pos), None);
+            sprite.toggle_behaviors(&[BehaviorType::Drag], true, now);
+
+            self.held = Some((entity, origin));
+        }
+    }
+
+    // 鼠标开始拖动时记录被拖动的精灵
+    fn hold_clicked(&mut self) {
+        if self.held.is_none() {
+            self.held = self
+                .sprites
+                .iter()
+                .find(|sprite| sprite.is_clicked() && sprite.has_behavior(BehaviorType::Drag))
+                .map(|sprite| (sprite.entity, sprite.get_transform().pos));
+        }
+    }
+
+    pub fn cancel_drag(&mut self) {
+        self.pressed.set(false);
+        self.dragging.set(false);
+        self.toggle_behaviors(&[BehaviorType::Drag], false);
+
+        if let Some((entity, origin)) = self.held.take() {
+            if let Some(sprite) = self.get_sprite_mut(entity) {
+                sprite.sprite.clickable = Some(Clickable { clicked: false });
+                sprite.update_transform(Some(origin), None);
+            }
+        }
+    }
+
     fn mouseenter_handler(&mut self, _x: f64, _y: f64) {}
 
     fn mousedonw_handler(&mut self, _x: f64, _y: f64) {
-        self.toggle_behaviors(&vec![BehaviorType::Click], true);
+        self.toggle_behaviors(&[BehaviorType::Click], true);
     }
 
+    // Click 在按下后的逻辑帧判定一次后自行停止，抬起时不取消，同一帧内的轻触也能生效
     fn mouseup_handler(&mut self, _x: f64, _y: f64) {
-        self.toggle_behaviors(&vec![BehaviorType::Click, BehaviorType::Drag], false);
+        self.toggle_behaviors(&[BehaviorType::Drag], false);
     }
 
     fn mousemove_handler(&mut self, _x: f64, _y: f64) {
-        self.toggle_behaviors(&vec![BehaviorType::Hover], true);
+        self.toggle_behaviors(&[BehaviorType::Hover], true);
     }
 
     fn mouseleave_handler(&mut self, _x: f64, _y: f64) {
-        self.toggle_behaviors(&vec![BehaviorType::Hover], false);
+        self.toggle_behaviors(&[BehaviorType::Hover], false);
     }
 
-    fn toggle_behaviors(&mut self, behavior_types: &Vec<BehaviorType>, flag: bool) {
+    pub fn toggle_animations(&mut self, playing: bool) {
         for sprite in &mut self.sprites {
-            sprite.toggle_behaviors(behavior_types, flag, self.now);
+            sprite.toggle_animation(playing);
         }
     }
 
-    pub fn collect_sun(&mut self) {
-        let sun_index = self.sprites.iter().position(|sprite| {
-            sprite.name() == SpriteType::Interface(Interface::Sun) && sprite.is_clicked()
-        });
+    pub fn toggle_behaviors(&mut self, behavior_types: &[BehaviorType], flag: bool) {
+        for sprite in &mut self.sprites {
+            sprite.toggle_behaviors(behavior_types, flag, self.now);
+        }
+    }
 
-        if let Some(sun_index) = sun_index {
-            self.sprites.remove(sun_index);
+    pub fn collect_sun(&mut self, entity: Entity) {
+        if let Some(sun) = self.remove_sprite(entity) {
+            self.pool.release(sun);
         }
 
         if self.state.cur_sun > 1 {
@@ -304,247 +643,376 @@ impl Game {
     }
 
     pub fn run(&mut self) {
-        self.now = self.time_system.calculate_game_time();
+        let ticks = self.time_system.advance();
 
-        if self.now - self.last_gc_time > self.sun_produce_rate {
-            self.last_gc_time = self.now;
+        for _ in 0..ticks {
+            self.tick();
+        }
 
-            if self.state.selected_card && self.state.cur_sun < self.state.max_sun {
+        self.fps.calc(self.time_system.real_time());
+        self.draw_sprites(self.time_system.alpha());
+    }
+
+    // 固定步长的逻辑帧，结果与渲染帧率无关
+    pub fn tick(&mut self) {
+        self.now = self.time_system.tick();
+
+        if let Some(scene) = self.current_scene() {
+            scene.scene().update(self);
+        }
+
+        self.apply_transitions();
+        self.fps.update(self.now);
+    }
+
+    // 场景默认的逻辑帧：定期回收不可见精灵，战斗中同时掉落阳光
+    pub fn update_world(&mut self, produce_sun: bool) {
+        if self.now - self.last_sun_time > self.sun_produce_rate {
+            self.last_sun_time = self.now;
+
+            if produce_sun && self.state.cur_sun < self.state.max_sun {
                 self.drop_sun(None);
                 self.state.cur_sun += 1;
             }
+        }
 
+        if self.now - self.last_gc_time > GC_INTERVAL {
+            self.last_gc_time = self.now;
             self.gc();
         }
 
-        self.fps.calc(self.now, self.time_rate);
         self.update();
-        self.fps.update(self.now);
     }
 
-    fn map_callback(&mut self, callback: Callback) -> ErasedFnPointer {
-        let pointer = match callback {
-            Callback::ShowReady => ErasedFnPointer::from_associated(self, Game::show_ready),
-            Callback::TurnToLevelPrepareScene => {
-                ErasedFnPointer::from_associated(self, Game::turn_to_level_prepare_scene)
-            }
-            Callback::ChoosePlantCard => {
-                ErasedFnPointer::from_associated(self, Game::choose_plant_card)
-            }
-            Callback::BattleSetup => ErasedFnPointer::from_associated(self, Game::battle_setup),
-            Callback::ResetCardSelect => {
-                ErasedFnPointer::from_associated(self, Game::reset_card_select)
-            }
-            Callback::SelectPlantSeed => {
-                ErasedFnPointer::from_associated(self, Game::select_plant_seed)
-            }
-            Callback::StartBattle => ErasedFnPointer::from_associated(self, Game::start_battle),
-            Callback::CollectSun => ErasedFnPointer::from_associated(self, Game::collect_sun),
+    /* 场景 */
+    pub fn current_scene(&self) -> Option<SceneKind> {
+        self.scenes.last().copied()
+    }
+
+    pub fn in_scene(&self, kind: SceneKind) -> bool {
+        self.current_scene() == Some(kind)
+    }
+
+    // 战斗中，或战斗被暂停（页面隐藏时会先自动暂停）
+    pub fn in_battle(&self) -> bool {
+        matches!(
+            self.scenes.as_slice(),
+            [.., SceneKind::Battle] | [.., SceneKind::Battle, SceneKind::Paused]
+        )
+    }
+
+    pub fn set_speed(&mut self, speed: Speed) {
+        self.speed = speed;
+        self.time_system.set_transducer(speed.transducer(), None);
+    }
+
+    // 临时变速（如慢动作），duration 毫秒后恢复为当前档位
+    pub fn set_temporary_transducer(&mut self, transducer: Transducer, duration: f64) {
+        self.time_system.set_transducer(transducer, Some(duration));
+    }
+
+    // 暂停覆盖在当前场景之上，期间游戏时间与所有行为的计时器都停止
+    pub fn pause(&mut self) {
+        if !self.is_paused() {
+            self.transition(Transition::Push(SceneKind::Paused));
+            self.apply_transitions();
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if self.is_paused() {
+            self.transition(Transition::Pop);
+            self.apply_transitions();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.in_scene(SceneKind::Paused)
+    }
+
+    pub fn in_result(&self) -> bool {
+        self.in_scene(SceneKind::Victory) || self.in_scene(SceneKind::Defeat)
+    }
+
+    // 移除结算场景，战斗换成主页
+    pub fn back_home(&mut self) {
+        self.transition(Transition::Pop);
+        self.transition(Transition::Replace(SceneKind::Home));
+        self.apply_transitions();
+    }
+
+    // 离开战斗时清空战场，再次进入时从头开始
+    pub fn end_battle(&mut self) {
+        self.clear_sprites();
+        self.seed_chooser.clear();
+        self.plant_cards.clear();
+        self.held = None;
+        self.sun = 150;
+        self.state = State {
+            guideline: self.state.guideline,
+            ..State::new()
         };
+    }
+
+    pub fn freeze(&mut self) {
+        self.time_system.pause();
 
-        return pointer;
+        for sprite in &mut self.sprites {
+            sprite.pause_behaviors(self.now);
+        }
     }
 
-    fn register_callbacks(
-        &mut self,
-        sprites: &mut Vec<SpriteWrap>,
-        sprite_type: SpriteType,
-        behavior_type: BehaviorType,
-        callbacks: &Vec<Callback>,
-    ) {
-        let behaviors = SpriteWrap::find_sprite_behaviors(sprites, sprite_type, behavior_type);
-        let mut i: usize = 0;
+    pub fn unfreeze(&mut self) {
+        for sprite in &mut self.sprites {
+            sprite.unpause_behaviors(self.now);
+        }
 
-        for behavior in behaviors {
-            let callback = match callbacks.get(i) {
-                Some(callback) => callback,
-                None => &callbacks[0],
-            };
-            let pointer = self.map_callback(*callback);
+        self.time_system.resume();
+    }
+
+    // 场景切换的唯一入口，避免在处理事件的途中修改场景栈
+    pub fn transition(&mut self, transition: Transition) {
+        self.transitions.push(transition);
+    }
+
+    // enter、exit 中排队的切换也在本次一并执行
+    pub fn apply_transitions(&mut self) {
+        while !self.transitions.is_empty() {
+            for transition in mem::take(&mut self.transitions) {
+                match transition {
+                    Transition::Push(kind) => {
+                        let from = self.current_scene();
 
-            behavior.set_cb(pointer);
-            i += 1;
+                        self.scenes.push(kind);
+                        kind.scene().enter(self, from);
+                    }
+                    Transition::Pop => {
+                        if let Some(kind) = self.scenes.pop() {
+                            kind.scene().exit(self);
+                        }
+                    }
+                    Transition::Replace(kind) => {
+                        let from = self.scenes.pop();
+
+                        if let Some(from) = from {
+                            from.scene().exit(self);
+                        }
+
+                        self.scenes.push(kind);
+                        kind.scene().enter(self, from);
+                    }
+                }
+            }
+        }
+    }
+
+    fn execute_callback(&mut self, callback: Callback, entity: Entity) {
+        match callback {
+            Callback::ShowReady => self.show_ready(),
+            Callback::TurnToLevelPrepareScene => {
+                self.transition(Transition::Replace(SceneKind::LevelIntro))
+            }
+            Callback::ChoosePlantCard => {
+                self.transition(Transition::Replace(SceneKind::SeedSelect))
+            }
+            Callback::BattleSetup => self.transition(Transition::Replace(SceneKind::Battle)),
+            Callback::PrepareBattle => self.prepare_battle(),
+            Callback::ResetCardSelect => self.reset_card_select(),
+            // 选卡还未实现，点击卡片不做处理
+            Callback::SelectPlantSeed => (),
+            Callback::StartBattle => self.start_battle(),
+            Callback::CollectSun => self.collect_sun(entity),
         }
     }
 
-    fn register_callback(
-        &mut self,
-        sprites: &mut Vec<SpriteWrap>,
+    pub fn register_callback(
+        sprites: &mut [SpriteWrap],
         sprite_type: SpriteType,
         behavior_type: BehaviorType,
         callback: Callback,
     ) {
-        let behavior = SpriteWrap::find_sprite_behavior(sprites, sprite_type, behavior_type);
+        let sprite = sprites
+            .iter_mut()
+            .find(|sprite| sprite.name() == sprite_type);
 
-        if let Some(behavior) = behavior {
-            let pointer = self.map_callback(callback);
+        if let Some(sprite) = sprite {
+            sprite.register_callback(behavior_type, callback);
+        }
+    }
+
+    // 逻辑帧末尾处理事件：先交给精灵自身，再执行其注册的回调
+    fn handle_events(&mut self) {
+        for event in self.events.drain() {
+            let entity = event.entity();
+            let sprite = self.get_sprite_mut(entity);
+
+            // 同一帧内先处理的事件可能已经移除了该精灵
+            let callback = match sprite {
+                Some(sprite) => {
+                    systems::handle_event(&mut sprite.sprite, &event);
+                    sprite.get_callback(event.behavior_type())
+                }
+                None => None,
+            };
 
-            behavior.set_cb(pointer);
+            if let Some(callback) = callback {
+                self.execute_callback(callback, entity);
+            }
         }
     }
 
     fn drop_sun(&mut self, pos: Option<&Pos>) {
-        let mut sun = LevelScene::drop_sun(&self.builder, pos);
+        let sun_data = LevelScene::sun_data(&self.builder, &mut self.rng, pos);
+        let mut sun = vec![self
+            .pool
+            .acquire(&self.builder, "interface", "Sun", sun_data)];
 
-        sun[0].toggle_behaviors(
-            &vec![BehaviorType::Cycle, BehaviorType::Walk],
-            true,
-            self.now,
-        );
+        sun[0].toggle_animation(true);
+        sun[0].toggle_behaviors(&[BehaviorType::Walk], true, self.now);
 
-        self.register_callback(
+        Game::register_callback(
             &mut sun,
             SpriteType::Interface(Interface::Sun),
             BehaviorType::Click,
             Callback::CollectSun,
         );
 
-        self.sprites.extend(sun);
+        self.add_sprites(sun);
     }
 
-    fn shoot(&mut self, pos: Pos, sprite_type: &SpriteType) {
-        let bullet = match sprite_type {
-            SpriteType::Plant(Plant::Peashooter) => Some("PB00"),
-            SpriteType::Plant(Plant::SnowPea) => Some("PB100"),
-            _ => None,
-        };
+    fn shoot(&mut self, pos: Pos, bullet: &str) {
+        let mut bullet_data = self.builder.get_data(&format!("plant/{}", bullet));
 
-        if let Some(bullet) = bullet {
-            let mut bullet_sprite = self.builder.from_name_and_pos("plant", bullet, vec![pos]);
+        bullet_data.pos = vec![pos];
 
-            for bullet in &mut bullet_sprite {
-                bullet.toggle_behaviors(&vec![BehaviorType::Walk], true, self.now);
-            }
+        let mut bullet_sprite = self
+            .pool
+            .acquire(&self.builder, "plant", bullet, bullet_data);
 
-            self.sprites.extend(bullet_sprite);
-        }
+        bullet_sprite.toggle_behaviors(&[BehaviorType::Walk], true, self.now);
+
+        self.add_sprite(bullet_sprite);
     }
 
     /* sprite 创建 */
     fn create_guideline(&mut self) {
-        self.sprites.push(Guideline::new());
+        self.add_sprite(Guideline::new());
     }
 
-    fn create_home_scene(&mut self) {
+    pub fn create_home_scene(&mut self) {
         let mut homes = HomeScene::create(&self.builder);
 
-        self.register_callback(
+        Game::register_callback(
             &mut homes,
             SpriteType::Interface(Interface::SelectorAdventureButton),
             BehaviorType::Click,
             Callback::ShowReady,
         );
 
-        self.register_callback(
+        Game::register_callback(
             &mut homes,
             SpriteType::Interface(Interface::SelectorZombieHand),
             BehaviorType::Frequency,
             Callback::TurnToLevelPrepareScene,
         );
 
-        self.sprites.extend(homes);
+        self.add_sprites(homes);
     }
 
-    fn create_level_background(&mut self) {
+    pub fn create_level_background(&mut self) {
         let mut level_scenes = LevelScene::create(&self.builder, &self.cur_level.scenes[0..1]);
 
-        self.register_callback(
+        Game::register_callback(
             &mut level_scenes,
             SpriteType::Interface(Interface::Background1),
             BehaviorType::Scroll,
             Callback::ChoosePlantCard,
         );
 
-        self.sprites.extend(level_scenes);
+        self.add_sprites(level_scenes);
     }
 
     fn create_level_scene(&mut self) {
         let mut level_scenes = LevelScene::create(&self.builder, &self.cur_level.scenes[1..]);
 
-        self.register_callback(
+        Game::register_callback(
             &mut level_scenes,
             SpriteType::Interface(Interface::PrepareGrowPlants),
             BehaviorType::Frequency,
             Callback::StartBattle,
         );
 
-        self.sprites.extend(level_scenes);
+        self.add_sprites(level_scenes);
     }
 
     fn create_flag(&mut self) {
         let flags = LevelScene::flag(&self.builder, self.cur_level.flag_num);
 
-        self.sprites.extend(flags);
+        self.add_sprites(flags);
     }
 
-    fn create_plant_card_left(&mut self) {
+    pub fn create_plant_card_left(&mut self) {
         let mut plant_card = LevelScene::plant_card(&self.builder, &self.cur_level.plant_cards);
 
         plant_card
             .iter_mut()
             .for_each(|card| card.update_scale(1.0));
 
-        self.sprites.extend(plant_card);
+        self.plant_cards = self.add_sprites(plant_card);
     }
 
     fn create_plants(&mut self) {
         let plants = LevelScene::plants(&self.builder, &self.cur_level.plants);
 
-        self.sprites.extend(plants);
+        self.add_sprites(plants);
     }
 
     fn create_zombies(&mut self) {
         let zombies = LevelScene::zombies(&self.builder, &self.cur_level.zombies);
 
-        self.sprites.extend(zombies);
+        self.add_sprites(zombies);
     }
 
     /* 回调 */
-    // 僵尸手动画结束转场
-    fn turn_to_level_prepare_scene(&mut self) {
-        self.sprites.clear();
-
-        self.create_level_background();
-        self.toggle_behaviors(&vec![BehaviorType::Scroll], true);
-    }
-
-    // 背景移动到最右/最左开始战斗
-    fn choose_plant_card(&mut self) {
-        if self.state.selected_card {
-            self.toggle_sunback();
-            self.create_flag();
-            self.create_guideline();
-            self.create_level_scene();
-
-            self.schedule_draw_order();
+    // 背景移回草坪，摆放关卡界面并开始准备动画
+    pub fn prepare_battle(&mut self) {
+        self.toggle_sunback();
+        self.create_flag();
+        self.create_guideline();
+        self.create_level_scene();
 
-            self.toggle_behaviors(&vec![BehaviorType::Frequency], true);
-        } else {
-            self.prepare_choose_card();
-        }
+        self.toggle_behaviors(&[BehaviorType::Frequency], true);
     }
 
     // 最右选择卡片
-    fn prepare_choose_card(&mut self) {
+    pub fn prepare_choose_card(&mut self) {
         let mut seed_chooser = LevelScene::seed_chooser(&self.builder);
         let mut plant_card = LevelScene::plant_seed(&self.builder, &self.cur_level.plant_cards);
 
-        self.register_callbacks(
-            &mut seed_chooser,
-            SpriteType::Interface(Interface::SelectCardButton),
-            BehaviorType::Click,
-            &vec![Callback::ResetCardSelect, Callback::BattleSetup],
-        );
+        let buttons = seed_chooser
+            .iter_mut()
+            .filter(|sprite| sprite.name() == SpriteType::Interface(Interface::SelectCardButton));
+
+        for (button, callback) in buttons.zip([Callback::ResetCardSelect, Callback::BattleSetup]) {
+            button.register_callback(BehaviorType::Click, callback);
+        }
 
         plant_card.iter_mut().for_each(|card| {
-            card.register_callback(
-                BehaviorType::Click,
-                self.map_callback(Callback::SelectPlantSeed),
-            )
+            card.register_callback(BehaviorType::Click, Callback::SelectPlantSeed)
         });
 
-        self.sprites.extend(seed_chooser);
-        self.sprites.extend(plant_card);
+        let seed_chooser = self.add_sprites(seed_chooser);
+        let plant_card = self.add_sprites(plant_card);
+
+        // 选卡结束后按句柄移除，SunBack 留在场上
+        self.seed_chooser = seed_chooser
+            .into_iter()
+            .chain(plant_card)
+            .filter(|entity| match self.get_sprite(*entity) {
+                Some(sprite) => sprite.name() != SpriteType::Interface(Interface::SunBack),
+                None => false,
+            })
+            .collect();
         self.create_plant_card_left();
     }
 
@@ -557,7 +1025,7 @@ impl Game {
             sun_back.sprite.toggle();
 
             if sun_back.is_visible() {
-                sun_back.update_draw_info(Some(Pos::new(100.0, 0.0)), None);
+                sun_back.update_transform(Some(Pos::new(100.0, 0.0)), None);
             }
         }
     }
@@ -565,13 +1033,12 @@ impl Game {
     fn start_battle(&mut self) {
         self.create_plants();
         self.create_zombies();
+        self.state.zombies_spawned = true;
         self.sunback();
 
-        // self.schedule_draw_order();
-
+        self.toggle_animations(true);
         self.toggle_behaviors(
-            &vec![
-                BehaviorType::Cycle,
+            &[
                 BehaviorType::Walk,
                 BehaviorType::Switch,
                 BehaviorType::Collision,
@@ -581,62 +1048,41 @@ impl Game {
         );
     }
 
-    fn battle_setup(&mut self) {
-        let mut remove_sprites = vec![
-            SpriteType::Interface(Interface::SeedChooserBackground),
-            SpriteType::Interface(Interface::SelectCardButton),
-            SpriteType::Interface(Interface::SelectCardButton),
-        ];
-
-        remove_sprites.extend(
-            self.cur_level
-                .plant_cards
-                .iter()
-                .map(|plant_card| SpriteType::from_str(plant_card)),
-        );
+    // 收起选卡界面
+    pub fn close_seed_chooser(&mut self) {
+        let seed_chooser = mem::take(&mut self.seed_chooser);
 
         self.toggle_sunback();
-        self.remove_sprites(&remove_sprites);
-        self.state.selected_card = true;
-        self.toggle_behaviors(&vec![BehaviorType::Scroll], true);
-    }
-
-    fn select_plant_seed(&mut self) {
-        unsafe { log!("选择植物") };
+        self.remove_sprites(&seed_chooser);
     }
 
     /* 行为激活 */
     fn show_ready(&mut self) {
-        self.state.home_ready = true;
-        self.toggle_behaviors(&vec![BehaviorType::Frequency], true);
+        self.toggle_behaviors(&[BehaviorType::Frequency], true);
     }
 
-    // TOOD：优化
+    // 产出阳光、发射子弹的精灵由 SunProducer、Shooter 组件决定
     fn before_update(&mut self) {
         let mut sun_poss: Vec<Pos> = vec![];
-        let mut bullet_poss: Vec<(Pos, SpriteType)> = vec![];
+        let mut bullets: Vec<(Pos, String)> = vec![];
 
         for sprite in &mut self.sprites {
-            match sprite.name() {
-                SpriteType::Plant(Plant::SunFlower1) => {
-                    let gened = sprite.sprite.get_gen_sun_flag();
-
-                    if gened {
-                        sun_poss.push(PlantSprite::get_sun_pos(&sprite.sprite));
-
-                        sprite.sprite.set_gen_sun_flag(false);
-                    }
-                }
-                SpriteType::Plant(Plant::Peashooter) | SpriteType::Plant(Plant::SnowPea) => {
-                    let shoot = sprite.sprite.get_shoot();
+            if let Some(sun_pos) = systems::take_sun(&mut sprite.sprite) {
+                sun_poss.push(sun_pos);
+            }
 
-                    if shoot {
-                        bullet_poss.push(PlantSprite::get_bullet_pos(&sprite.sprite));
+            // 同一行前方有僵尸时才发射
+            let has_target = match (&sprite.sprite.shooter, sprite.sprite.lane) {
+                (Some(_), Some(lane)) => self
+                    .spatial_index
+                    .ahead(Layer::Zombie, lane.row, sprite.sprite.transform.pos.left)
+                    .next()
+                    .is_some(),
+                _ => false,
+            };
 
-                        sprite.sprite.set_shoot(false);
-                    }
-                }
-                _ => (),
+            if let Some(bullet) = systems::take_shot(&mut sprite.sprite, has_target) {
+                bullets.push(bullet);
             }
         }
 
@@ -644,17 +1090,22 @@ impl Game {
             self.drop_sun(Some(sun_pos));
         }
 
-        for (pos, sprite_type) in &bullet_poss {
-            self.shoot(*pos, sprite_type)
+        for (pos, bullet) in &bullets {
+            self.shoot(*pos, bullet)
         }
     }
 
     /* 绘制、行为处理 */
     fn update(&mut self) {
+        for sprite in &mut self.sprites {
+            sprite.save_transform();
+        }
+
         self.before_update();
         self.check_collision();
+        self.check_hits();
         self.update_spirte_behaviors();
-        self.draw_sprites();
+        self.handle_events();
     }
 
     fn update_spirte_behaviors(&mut self) {
@@ -666,64 +1117,182 @@ impl Game {
                 self.now,
                 self.fps.last_animation_frame_time,
                 cur_pos,
-                &self.context,
+                &mut self.events,
             );
         }
     }
 
-    fn collect_collision_sprites(&self) -> Vec<&Box<dyn Update>> {
-        let mut sprites: Vec<&Box<dyn Update>> = vec![];
+    // 只与同一行、左侧相邻格子里的植物比较
+    fn check_collision(&mut self) {
+        let mut results: Vec<(Entity, Option<Entity>)> = vec![];
 
-        self.sprites
+        self.spatial_index.sync(&self.sprites);
+
+        for sprite in self
+            .sprites
             .iter()
             .filter(|sprite| sprite.can_check_collision())
-            .for_each(|sprite| sprites.push(&sprite.sprite));
+        {
+            let (row, col) = sprite.get_loc();
+            let candidates: Vec<&SpriteWrap> = self
+                .spatial_index
+                .query(Layer::Plant, row, col.saturating_sub(1)..=col)
+                .filter_map(|entity| self.get_sprite(entity))
+                .collect();
+            let candidate_sprites: Vec<&Sprite> =
+                candidates.iter().map(|sprite| &sprite.sprite).collect();
+            let other = systems::find_collision(&sprite.sprite, &candidate_sprites)
+                .map(|index| candidates[index].entity);
+
+            results.push((sprite.entity, other));
+        }
 
-        return sprites;
-    }
+        for (entity, other) in results {
+            let mut attack = 0.0;
 
-    fn collect_candidate_sprites(&self) -> Vec<&Box<dyn Update>> {
-        let mut candidate_sprites: Vec<&Box<dyn Update>> = vec![];
+            if let Some(sprite) = self.get_sprite_mut(entity) {
+                systems::update_collision(&mut sprite.sprite, other.is_some());
+                attack = systems::attack_damage(&sprite.sprite, TICK_DURATION);
+            }
 
-        self.sprites
-            .iter()
-            .filter(|sprite| sprite.can_candidate_for_collision())
-            .for_each(|sprite| candidate_sprites.push(&sprite.sprite));
+            if let Some(other) = other {
+                // 僵尸每个逻辑帧啃食碰到的植物
+                if let Some(plant) = self.get_sprite_mut(other) {
+                    systems::damage(&mut plant.sprite, attack);
+                }
 
-        return candidate_sprites;
+                self.events.push(GameEvent::Collided(entity, other));
+            }
+        }
     }
 
-    fn check_collision(&self) {
-        let sprites = self.collect_collision_sprites();
+    // 子弹只与同一行、前后相邻格子里的僵尸比较，每颗子弹只击中一个僵尸
+    fn check_hits(&mut self) {
+        let mut hits: Vec<(Entity, Entity)> = vec![];
+
+        for zombie in self.sprites.iter().filter(|sprite| sprite.is_zombie()) {
+            let (row, col) = zombie.get_loc();
+            let projectile = self
+                .spatial_index
+                .query(Layer::Projectile, row, col.saturating_sub(1)..=col + 1)
+                .filter(|entity| hits.iter().all(|(hit, _)| hit != entity))
+                .find(|entity| match self.get_sprite(*entity) {
+                    Some(projectile) => systems::did_hit(&projectile.sprite, &zombie.sprite),
+                    None => false,
+                });
+
+            if let Some(projectile) = projectile {
+                hits.push((projectile, zombie.entity));
+            }
+        }
 
-        if sprites.len() != 0 {
-            let candidate_sprites = self.collect_candidate_sprites();
+        for (projectile, zombie) in hits {
+            let attack = match self.get_sprite_mut(projectile) {
+                Some(projectile) => systems::hit(&mut projectile.sprite),
+                None => continue,
+            };
 
-            for sprite in sprites {
-                sprite.check_collision(&candidate_sprites);
+            if let Some(zombie) = self.get_sprite_mut(zombie) {
+                systems::damage(&mut zombie.sprite, attack);
             }
         }
     }
 
-    fn draw_sprites(&self) {
-        self.sprites
+    // 按图层从下往上绘制，阳光数量属于 HUD，画在覆盖层之前
+    pub fn draw_sprites(&mut self, alpha: f64) {
+        let mut draw_order: Vec<usize> = self
+            .sprites
+            .iter()
+            .enumerate()
+            // 只有辅助线的类型为 Nil
+            .filter(|(_, sprite)| {
+                sprite.is_visible() && (self.state.guideline || sprite.name() != SpriteType::Nil)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let mut sun_num_drawn = false;
+
+        draw_order.sort_by(|a, b| RenderLayer::compare(&self.sprites[*a], &self.sprites[*b]));
+
+        let background = draw_order
             .iter()
-            .filter(|sprite| sprite.is_visible())
-            .for_each(|sprite| sprite.draw(&self.context));
+            .position(|index| RenderLayer::of(&self.sprites[*index]) != RenderLayer::Background)
+            .unwrap_or(draw_order.len());
+
+        self.draw_background(&draw_order[..background], alpha);
+
+        for index in draw_order.into_iter().skip(background) {
+            if !sun_num_drawn && RenderLayer::of(&self.sprites[index]) == RenderLayer::Overlay {
+                self.draw_sun_num();
+                sun_num_drawn = true;
+            }
+
+            self.sprites[index].draw(self.renderer.as_mut(), alpha);
+        }
+
+        if !sun_num_drawn {
+            self.draw_sun_num();
+        }
+
+        for kind in self.scenes.clone() {
+            kind.scene().draw(self);
+        }
+    }
+
+    // 背景层没有变化时直接贴上次的离屏缓存
+    fn draw_background(&mut self, indices: &[usize], alpha: f64) {
+        let all = &self.sprites;
+        let sprites: Vec<&SpriteWrap> = indices.iter().map(|index| &all[*index]).collect();
+
+        if !self.renderer.can_cache() {
+            for sprite in sprites {
+                sprite.draw(self.renderer.as_mut(), alpha);
+            }
 
-        self.draw_sun_num();
+            return;
+        }
+
+        if self.background_cache.refresh(&sprites, alpha) {
+            self.renderer.begin_cache();
+
+            for sprite in sprites {
+                sprite.draw(self.renderer.as_mut(), alpha);
+            }
+
+            self.renderer.end_cache();
+        }
+
+        self.renderer.draw_cache();
     }
 
-    fn draw_sun_num(&self) {
+    fn draw_sun_num(&mut self) {
         let num = match self.sun > 99999 {
             true => String::from("9999+"),
             false => self.sun.to_string(),
         };
 
-        self.context.save();
-        self.context.set_font("32px 黑体");
-        self.context.fill_text(&num, 138.0, 30.0).unwrap();
-        self.context.restore();
+        self.renderer.save();
+        self.renderer.set_font("32px 黑体");
+        self.renderer.fill_text(&num, 138.0, 30.0);
+        self.renderer.restore();
+    }
+
+    // 僵尸走出草坪左侧时被 Walk 隐藏，在下一次回收前判定；右侧出界的同样会被隐藏，
+    // 被打死的僵尸也会隐藏，需要排除
+    pub fn zombie_reached_house(&self) -> bool {
+        self.sprites.iter().any(|sprite| {
+            sprite.is_zombie()
+                && !sprite.is_visible()
+                && sprite.sprite.is_alive()
+                && sprite.get_transform().pos.left < COL_X_COORD[0]
+        })
+    }
+
+    pub fn zombie_count(&self) -> usize {
+        self.sprites
+            .iter()
+            .filter(|sprite| sprite.is_zombie())
+            .count()
     }
 
     fn gc(&mut self) {
@@ -0,0 +1,194 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::rc::Rc;
+use std::{fs, io, path::Path};
+
+use crate::{
+    data::{CANVAS_HEIGHT, CANVAS_WIDTH, MANIFEST_JSON},
+    game::Game,
+    manifest::Manifest,
+    model::{Event, LevelData},
+    renderer::{headless::HeadlessRenderer, Renderer},
+    replay::Replay,
+    snapshot::{Snapshot, SnapshotError},
+    sprites::model::{Pos, SpriteCell, SpriteData},
+    timer::clock::ManualClock,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{log, renderer::software::RgbaImage};
+
+// 浏览器之外驱动游戏：时间由 ManualClock 推进，绘制交给任意 Renderer
+pub struct Headless {
+    pub game: Game,
+    pub clock: ManualClock,
+}
+
+impl Default for Headless {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Headless {
+    pub fn new() -> Headless {
+        Headless::with_renderer(Box::new(HeadlessRenderer::new(CANVAS_WIDTH, CANVAS_HEIGHT)))
+    }
+
+    pub fn with_renderer(renderer: Box<dyn Renderer>) -> Headless {
+        let clock = ManualClock::new();
+        let game = Game::new(renderer, Box::new(clock.clone()));
+
+        Headless { game, clock }
+    }
+
+    pub fn manifest(root: &Path) -> io::Result<Manifest> {
+        let json = fs::read_to_string(root.join(MANIFEST_JSON))?;
+
+        Manifest::new_from_json(&json).map_err(Headless::invalid_data)
+    }
+
+    fn invalid_data(e: serde_json::Error) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+
+    // root 为仓库根目录，资源路径与浏览器端一致
+    pub fn load(&mut self, root: &Path) -> io::Result<()> {
+        let read = |path: &str| fs::read_to_string(root.join(path));
+        let manifest = Headless::manifest(root)?;
+        let builder = &mut self.game.builder;
+
+        for path in manifest.cells.iter() {
+            builder
+                .cells
+                .extend(SpriteCell::new_from_json(&read(path)?).map_err(Headless::invalid_data)?);
+        }
+
+        for path in manifest.data.iter() {
+            builder
+                .data
+                .extend(SpriteData::new_from_json(&read(path)?).map_err(Headless::invalid_data)?);
+        }
+
+        for path in manifest.levels.iter() {
+            self.game
+                .leval_data
+                .extend(LevelData::new_from_json(&read(path)?).map_err(Headless::invalid_data)?);
+        }
+
+        for path in manifest.outlines.iter() {
+            self.game
+                .builder
+                .outlines
+                .extend(Pos::outlines_from_json(&read(path)?).map_err(Headless::invalid_data)?);
+        }
+
+        let errors = self.game.builder.validate(&self.game.leval_data);
+
+        if let Some(e) = errors.first() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, e.to_string()));
+        }
+
+        if let Some(level) = self.game.leval_data.first() {
+            self.game.cur_level = level.clone();
+        }
+
+        Ok(())
+    }
+
+    // 解码精灵图，供 SoftwareRenderer 绘制；与浏览器端一样跳过加载失败的图片
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_sheets(&mut self, root: &Path) {
+        let sheets = match Headless::manifest(root) {
+            Ok(manifest) => manifest.sheets,
+            Err(e) => {
+                log!("failed to load {}: {}", MANIFEST_JSON, e);
+                return;
+            }
+        };
+
+        for sheet in sheets.iter() {
+            match RgbaImage::open(&root.join(&sheet.path)) {
+                Ok(image) => {
+                    self.game
+                        .builder
+                        .sheets
+                        .insert(sheet.name.to_string(), Rc::new(image));
+                }
+                Err(e) => log!("failed to load {}: {}", sheet.path, e),
+            }
+        }
+    }
+
+    pub fn start(&mut self) {
+        self.game.init();
+    }
+
+    // 代替 start，从对局中途的快照继续
+    pub fn resume(&mut self, snapshot: &Snapshot) -> Result<(), SnapshotError> {
+        self.game.load_snapshot(snapshot)
+    }
+
+    pub fn step(&mut self, delta: f64) {
+        self.clock.advance(delta);
+        self.game.run();
+    }
+
+    pub fn run_for(&mut self, duration: f64, frame: f64) {
+        let mut elapsed = 0.0;
+
+        while elapsed < duration {
+            self.step(frame);
+            elapsed += frame;
+        }
+    }
+
+    // 逐个逻辑帧推进到 tick，不经过时钟，保证与录制时的逻辑帧一一对应
+    pub fn run_ticks_until(&mut self, tick: u64) {
+        while self.game.time_system.ticks < tick {
+            self.game.tick();
+        }
+    }
+
+    // 在刚 load 完、尚未 start 的 Game 上按录制的种子与关卡回放
+    pub fn replay(&mut self, replay: &Replay) -> io::Result<()> {
+        let level = self
+            .game
+            .leval_data
+            .iter()
+            .find(|level| level.name == replay.level)
+            .cloned()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("level {} not found", replay.level),
+                )
+            })?;
+
+        self.game.cur_level = level;
+        self.game.first_scene = replay.scene;
+        self.game.set_seed(replay.seed);
+        self.start();
+
+        for input in &replay.inputs {
+            self.run_ticks_until(input.tick);
+            match input.action {
+                Some(action) => {
+                    self.game.cur_pos.set(Pos::new(input.x, input.y));
+                    self.game.dispatch_action(action);
+                }
+                None => self.game.dispatch_event(input.event, input.x, input.y),
+            }
+        }
+
+        self.run_ticks_until(replay.ticks);
+        self.game.draw_sprites(0.0);
+
+        Ok(())
+    }
+
+    pub fn click(&mut self, pos: &Pos, frame: f64) {
+        self.game
+            .dispatch_event(Event::Mousedown, pos.left, pos.top);
+        self.step(frame);
+        self.game.dispatch_event(Event::Mouseup, pos.left, pos.top);
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+// 按键触发的操作，录像中记录操作而不是按键，与各自的键位设置无关
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    // 拿起左侧第 n 张植物卡片（从 0 开始）
+    SelectSeed(usize),
+    Shovel,
+    Pause,
+    CancelDrag,
+    ToggleGuideline,
+    CycleSpeed,
+}
+
+/* Keymap */
+// 键名与 KeyboardEvent.key 一致，单个字符不区分大小写
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<String, Action>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Keymap {
+    pub fn new() -> Keymap {
+        let mut keymap = Keymap {
+            bindings: HashMap::new(),
+        };
+
+        for index in 0..9 {
+            keymap.bind(&(index + 1).to_string(), Action::SelectSeed(index));
+        }
+
+        keymap.bind("s", Action::Shovel);
+        keymap.bind(" ", Action::Pause);
+        keymap.bind("Escape", Action::CancelDrag);
+        keymap.bind("g", Action::ToggleGuideline);
+        keymap.bind("f", Action::CycleSpeed);
+
+        keymap
+    }
+
+    // 完整替换默认键位，例如 {"p": "Pause", "q": {"SelectSeed": 0}}
+    pub fn new_from_json(json: &str) -> serde_json::Result<Keymap> {
+        let bindings: HashMap<String, Action> = serde_json::from_str(json)?;
+        let mut keymap = Keymap {
+            bindings: HashMap::new(),
+        };
+
+        for (key, action) in bindings {
+            keymap.bind(&key, action);
+        }
+
+        Ok(keymap)
+    }
+
+    fn normalize(key: &str) -> String {
+        match key.chars().count() {
+            1 => key.to_lowercase(),
+            _ => key.to_string(),
+        }
+    }
+
+    pub fn bind(&mut self, key: &str, action: Action) {
+        self.bindings.insert(Keymap::normalize(key), action);
+    }
+
+    pub fn unbind(&mut self, key: &str) {
+        self.bindings.remove(&Keymap::normalize(key));
+    }
+
+    pub fn get(&self, key: &str) -> Option<Action> {
+        self.bindings.get(&Keymap::normalize(key)).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_bindings() {
+        let keymap = Keymap::new();
+
+        assert_eq!(keymap.get("1"), Some(Action::SelectSeed(0)));
+        assert_eq!(keymap.get("9"), Some(Action::SelectSeed(8)));
+        assert_eq!(keymap.get("0"), None);
+        assert_eq!(keymap.get("s"), Some(Action::Shovel));
+        assert_eq!(keymap.get(" "), Some(Action::Pause));
+        assert_eq!(keymap.get("Escape"), Some(Action::CancelDrag));
+        assert_eq!(keymap.get("g"), Some(Action::ToggleGuideline));
+        assert_eq!(keymap.get("f"), Some(Action::CycleSpeed));
+    }
+
+    // 单个字符不区分大小写，多字符键名保持原样
+    #[test]
+    fn single_characters_ignore_case() {
+        let mut keymap = Keymap::new();
+
+        assert_eq!(keymap.get("S"), Some(Action::Shovel));
+        assert_eq!(keymap.get("escape"), None);
+
+        keymap.bind("P", Action::Pause);
+        assert_eq!(keymap.get("p"), Some(Action::Pause));
+
+        keymap.unbind("S");
+        assert_eq!(keymap.get("s"), None);
+    }
+
+    // 自定义键位完整替换默认键位
+    #[test]
+    fn json_replaces_the_defaults() {
+        let keymap = Keymap::new_from_json(r#"{"P": "Pause", "q": {"SelectSeed": 2}}"#).unwrap();
+
+        assert_eq!(keymap.get("p"), Some(Action::Pause));
+        assert_eq!(keymap.get("Q"), Some(Action::SelectSeed(2)));
+        assert_eq!(keymap.get(" "), None);
+        assert_eq!(keymap.get("1"), None);
+    }
+
+    #[test]
+    fn invalid_json_is_an_error() {
+        assert!(Keymap::new_from_json(r#"{"p": "Jump"}"#).is_err());
+        assert!(Keymap::new_from_json("[]").is_err());
+    }
+}
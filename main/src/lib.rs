@@ -1,25 +1,42 @@
 use wasm_bindgen::prelude::*;
 
 use engine::Engine;
+use time_system::Speed;
+#[cfg(debug_assertions)]
 use util::init;
 
 pub mod artists;
-mod behavior;
-mod builder;
-mod callback;
-mod data;
-mod engine;
-mod fps;
-mod game;
-mod loader;
-mod loc;
-mod marching_squares;
-mod model;
-mod scene;
-mod sprites;
-mod time_system;
-mod timer;
-mod util;
+pub mod atlas;
+pub mod behavior;
+pub mod builder;
+pub mod data;
+pub mod engine;
+pub mod entity;
+pub mod event;
+pub mod fps;
+pub mod game;
+pub mod headless;
+pub mod keymap;
+pub mod loader;
+pub mod loc;
+pub mod manifest;
+pub mod marching_squares;
+pub mod model;
+pub mod pointer;
+pub mod polygon;
+pub mod pool;
+pub mod renderer;
+pub mod replay;
+pub mod rng;
+pub mod scene;
+pub mod snapshot;
+pub mod spatial;
+pub mod sprites;
+pub mod systems;
+pub mod time_system;
+pub mod timer;
+pub mod util;
+pub mod viewport;
 
 #[wasm_bindgen(start)]
 pub fn run() -> Result<(), JsValue> {
@@ -30,3 +47,40 @@ pub fn run() -> Result<(), JsValue> {
 
     Ok(())
 }
+
+// 导出本局录像（JSON），附在问题报告中即可用 replay 示例复现
+#[wasm_bindgen]
+pub fn export_replay() -> Option<String> {
+    Engine::export_replay()
+}
+
+#[wasm_bindgen]
+pub fn pause() {
+    Engine::set_paused(true);
+}
+
+#[wasm_bindgen]
+pub fn resume() {
+    Engine::set_paused(false);
+}
+
+// 设置游戏速度，rate 为 0.5、1、2 或 3
+#[wasm_bindgen]
+pub fn set_speed(rate: f64) -> bool {
+    match Speed::from_rate(rate) {
+        Some(speed) => Engine::set_speed(speed),
+        None => false,
+    }
+}
+
+// 半速慢动作，duration 毫秒（真实时间）后恢复为当前档位
+#[wasm_bindgen]
+pub fn slow_motion(duration: f64) -> bool {
+    Engine::slow_motion(duration)
+}
+
+// 替换键位表，例如 set_keymap('{"p": "Pause", "q": {"SelectSeed": 0}}')
+#[wasm_bindgen]
+pub fn set_keymap(json: &str) -> bool {
+    Engine::set_keymap(json)
+}
@@ -12,7 +12,7 @@ use web_sys::HtmlImageElement;
 /// that will resolve when the image has fully loaded.
 ///
 /// Example:
-/// ```rust
+/// ```ignore
 /// let image = ImageFuture::new("assets/sprite_sheet.png").await;
 /// ```
 ///
@@ -82,5 +82,5 @@ impl Future for ImageFuture {
 pub async fn _load_image(path: &str) -> HtmlImageElement {
     let image = ImageFuture::new(path).await;
 
-    return image.unwrap();
+    image.unwrap()
 }
@@ -1,60 +1,83 @@
-use std::{collections::HashMap, rc::Rc};
+use std::{collections::HashMap, io, rc::Rc};
 
 use futures::future::join_all;
-use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{HtmlImageElement, Request, RequestInit, RequestMode, Response};
 
-use crate::engine::EngineError;
+use crate::{
+    engine::EngineError,
+    manifest::{AssetError, Manifest},
+};
 
 use self::image::ImageFuture;
 
 mod image;
 
-pub struct Loader<'a> {
-    pub cells: Vec<&'a str>,
-    pub data: Vec<&'a str>,
-    pub level: Vec<&'a str>,
-    images: Vec<(&'a str, &'a str)>,
+pub struct Loader {
+    pub manifest: Manifest,
 }
 
-impl<'a> Loader<'a> {
-    pub fn new(
-        cells: Vec<&'a str>,
-        data: Vec<&'a str>,
-        level: Vec<&'a str>,
-        images: Vec<(&'a str, &'a str)>,
-    ) -> Loader<'a> {
-        Loader {
-            cells,
-            data,
-            level,
-            images,
-        }
+// 每个文件加载完成后调用，失败时带上错误
+pub type OnLoaded<'a> = &'a dyn Fn(&str, Option<AssetError>);
+
+impl Loader {
+    pub fn new(manifest: Manifest) -> Loader {
+        Loader { manifest }
     }
 
-    pub async fn load_jsons(&self, paths: &Vec<&'a str>) -> Vec<JsValue> {
-        let mut jsons: Vec<JsValue> = vec![];
+    // 先读取资源清单，其余资源按清单加载
+    pub async fn from_manifest(url: &str) -> Result<Loader, AssetError> {
+        let json = Loader::load_json(url).await?;
+        let manifest = Manifest::new_from_json(&json).map_err(|e| AssetError::Parse {
+            path: url.to_string(),
+            reason: e.to_string(),
+        })?;
 
-        for json in paths.iter() {
-            let result = self.load_json(json).await;
+        Ok(Loader::new(manifest))
+    }
 
-            if let Ok(value) = result {
-                jsons.push(value);
+    // 返回加载成功的文件路径与内容，失败的通过 on_loaded 报告
+    pub async fn load_jsons(
+        &self,
+        paths: &[String],
+        on_loaded: OnLoaded<'_>,
+    ) -> Vec<(String, String)> {
+        let mut jsons: Vec<(String, String)> = vec![];
+
+        for path in paths.iter() {
+            match Loader::load_json(path).await {
+                Ok(json) => {
+                    on_loaded(path, None);
+                    jsons.push((path.to_string(), json));
+                }
+                Err(e) => on_loaded(path, Some(e)),
             }
         }
 
         jsons
     }
 
-    pub async fn load_json(&self, url: &str) -> Result<JsValue, EngineError> {
+    pub async fn load_json(url: &str) -> Result<String, AssetError> {
+        return Loader::fetch_text(url)
+            .await
+            .map_err(|e| AssetError::Fetch {
+                path: url.to_string(),
+                reason: match e {
+                    EngineError::IO(e) => e.to_string(),
+                    EngineError::Js(e) => e.as_string().unwrap_or_else(|| format!("{:?}", e)),
+                },
+            });
+    }
+
+    async fn fetch_text(url: &str) -> Result<String, EngineError> {
         // https://rustwasm.github.io/wasm-bindgen/examples/fetch.html
-        let mut opts = RequestInit::new();
+        let opts = RequestInit::new();
 
-        opts.method("GET");
-        opts.mode(RequestMode::Cors);
+        opts.set_method("GET");
+        opts.set_mode(RequestMode::Cors);
 
-        let request = Request::new_with_str_and_init(&url, &opts)?;
+        let request = Request::new_with_str_and_init(url, &opts)?;
 
         let window = web_sys::window().unwrap();
         let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
@@ -62,26 +85,49 @@ impl<'a> Loader<'a> {
         assert!(resp_value.is_instance_of::<Response>());
         let resp: Response = resp_value.dyn_into().unwrap();
 
-        let json = JsFuture::from(resp.json()?).await?;
+        // 404 等响应同样会 resolve，内容不是所需的文件
+        if !resp.ok() {
+            return Err(EngineError::IO(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("HTTP {}", resp.status()),
+            )));
+        }
 
-        Ok(json)
-    }
+        let json = JsFuture::from(resp.text()?).await?;
 
-    pub async fn load_images(&self) -> HashMap<String, Rc<HtmlImageElement>> {
-        let image_futures: Vec<ImageFuture> = self
-            .images
-            .iter()
-            .map(|path| ImageFuture::new(&(path.0.to_string() + path.1)))
-            .collect();
+        Ok(json.as_string().unwrap_or_default())
+    }
 
-        let future: Vec<Result<HtmlImageElement, ()>> = join_all(image_futures).await;
+    // 按精灵图名称返回加载成功的图片，失败的通过 on_loaded 报告
+    pub async fn load_images(
+        &self,
+        on_loaded: OnLoaded<'_>,
+    ) -> HashMap<String, Rc<HtmlImageElement>> {
+        let image_futures = self.manifest.sheets.iter().map(|sheet| async move {
+            let image = ImageFuture::new(&sheet.path).await;
+
+            match image {
+                Ok(image) => {
+                    on_loaded(&sheet.path, None);
+                    Some((sheet.name.to_string(), Rc::new(image)))
+                }
+                Err(()) => {
+                    on_loaded(
+                        &sheet.path,
+                        Some(AssetError::Image {
+                            path: sheet.path.to_string(),
+                            reason: String::from("load error"),
+                        }),
+                    );
+                    None
+                }
+            }
+        });
 
-        let images: HashMap<String, Rc<HtmlImageElement>> = self
-            .images
-            .iter()
-            .zip(future.into_iter())
-            .filter(|(_key, value)| (*value).is_ok())
-            .map(|(key, value)| (key.0.to_string(), Rc::new(value.unwrap())))
+        let images: HashMap<String, Rc<HtmlImageElement>> = join_all(image_futures)
+            .await
+            .into_iter()
+            .flatten()
             .collect();
 
         images
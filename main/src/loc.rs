@@ -17,14 +17,14 @@ impl Loc {
         pos.left += (pos2.left - pos.left - width) / 2.0;
         pos.top = pos2.top - 5.0 - height;
 
-        return pos;
+        pos
     }
 
     pub fn put_on(row: usize, col: usize) -> Pos {
         let x = COL_X_COORD[col];
         let y = ROW_Y_COORD[row];
 
-        return Pos::new(x, y);
+        Pos::new(x, y)
     }
 
     pub fn _put_any(left: f64, top: f64) -> Pos {
@@ -51,7 +51,7 @@ impl Loc {
             ));
         }
 
-        return pos;
+        pos
     }
 
     pub fn put_increase_y(x: f64, begin: f64, step: f64, count: usize) -> Vec<Pos> {
@@ -61,7 +61,7 @@ impl Loc {
             pos.push(Pos::new(x, begin + i as f64 * step));
         }
 
-        return pos;
+        pos
     }
 
     pub fn get_col_by_x(x: f64) -> usize {
@@ -83,6 +83,6 @@ impl Loc {
     }
 
     pub fn get_row_col_by_pos(pos: &Pos) -> (usize, usize) {
-        return (Loc::get_row_by_y(pos.top), Loc::get_col_by_x(pos.left));
+        (Loc::get_row_by_y(pos.top), Loc::get_col_by_x(pos.left))
     }
 }
@@ -0,0 +1,117 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/* SheetEntry */
+// 精灵图名称即帧数据、精灵数据键名的前缀，如 plant/PB00
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SheetEntry {
+    pub name: String,
+    pub path: String,
+}
+
+/* Manifest */
+// 启动时读取的资源清单，路径相对于站点根目录（原生环境为仓库根目录）；
+// 新增精灵图、关卡只需修改清单，不用重新编译
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub sheets: Vec<SheetEntry>,
+    #[serde(default)]
+    pub cells: Vec<String>,
+    #[serde(default)]
+    pub data: Vec<String>,
+    #[serde(default)]
+    pub levels: Vec<String>,
+    // 由 build_outlines 生成
+    #[serde(default)]
+    pub outlines: Vec<String>,
+}
+
+impl Manifest {
+    pub fn new_from_json(json: &str) -> Result<Manifest, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    // 需要加载的文件总数，清单本身不计入
+    pub fn len(&self) -> usize {
+        self.sheets.len()
+            + self.cells.len()
+            + self.data.len()
+            + self.levels.len()
+            + self.outlines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/* AssetError */
+// 资源加载、解析与校验的错误，指明出错的文件或键名
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssetError {
+    // 请求失败或文件不存在
+    Fetch {
+        path: String,
+        reason: String,
+    },
+    // 文件内容无法解析
+    Parse {
+        path: String,
+        reason: String,
+    },
+    // 图片无法加载或解码
+    Image {
+        path: String,
+        reason: String,
+    },
+    // 精灵数据或关卡引用了不存在的帧数据
+    MissingCells {
+        key: String,
+        by: String,
+    },
+    // 关卡中的名称没有对应的植物或僵尸数据
+    UnknownSprite {
+        name: String,
+        by: String,
+    },
+    // 行为缺少必需的参数，例如 Interval 没有 interval
+    MissingField {
+        field: String,
+        by: String,
+    },
+    // 关卡中的行列超出草坪坐标
+    OutOfLawn {
+        name: String,
+        row: usize,
+        col: usize,
+        by: String,
+    },
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssetError::Fetch { path, reason } => write!(f, "failed to fetch {}: {}", path, reason),
+            AssetError::Parse { path, reason } => write!(f, "invalid {}: {}", path, reason),
+            AssetError::Image { path, reason } => {
+                write!(f, "failed to load image {}: {}", path, reason)
+            }
+            AssetError::MissingCells { key, by } => {
+                write!(f, "cells {} not found, required by {}", key, by)
+            }
+            AssetError::UnknownSprite { name, by } => {
+                write!(f, "unknown sprite {} in {}", name, by)
+            }
+            AssetError::MissingField { field, by } => {
+                write!(f, "field {} is missing in {}", field, by)
+            }
+            AssetError::OutOfLawn { name, row, col, by } => write!(
+                f,
+                "{} at row {} col {} is outside the lawn in {}",
+                name, row, col, by
+            ),
+        }
+    }
+}
@@ -17,11 +17,11 @@ impl MarchingSquares {
         }
     }
 
-    pub fn get(&self, data: &Vec<u8>, width: i32, height: i32) -> Vec<Pos> {
-        return self.get_blob_outline_points(data, width, height);
+    pub fn get(&self, data: &[u8], width: i32, height: i32) -> Vec<Pos> {
+        self.get_blob_outline_points(data, width, height)
     }
 
-    fn get_blob_outline_points(&self, data4: &Vec<u8>, width: i32, height: i32) -> Vec<Pos> {
+    fn get_blob_outline_points(&self, data4: &[u8], width: i32, height: i32) -> Vec<Pos> {
         let size = width * height;
         let mut data: Vec<u8> = vec![0; size as usize];
 
@@ -32,36 +32,34 @@ impl MarchingSquares {
         let starting_point = self.get_first_non_transparent_pixel_top_down(&data, width, height);
 
         if let Some(starting_point) = starting_point {
-            return self.walk_perimeter(&data, width, height, starting_point.0, starting_point.1);
+            self.walk_perimeter(&data, width, height, starting_point.0, starting_point.1)
         } else {
-            return vec![];
+            vec![]
         }
     }
 
     fn get_first_non_transparent_pixel_top_down(
         &self,
-        data: &Vec<u8>,
+        data: &[u8],
         width: i32,
         height: i32,
     ) -> Option<(i32, i32)> {
         for h in 0..height {
-            let mut idx = h * width;
+            let row = h * width;
 
             for w in 0..width {
-                if data[idx as usize] > 0 {
+                if data[(row + w) as usize] > 0 {
                     return Some((w, h));
                 }
-
-                idx += 1;
             }
         }
 
-        return None;
+        None
     }
 
     fn walk_perimeter(
         &self,
-        data: &Vec<u8>,
+        data: &[u8],
         width: i32,
         height: i32,
         start_w: i32,
@@ -101,19 +99,23 @@ impl MarchingSquares {
             }
         }
 
-        point_list.push(Pos::new(self.offset_x + w as f64, self.offset_x + h as f64));
+        // 回到起点，闭合轮廓
+        point_list.push(Pos::new(
+            self.offset_x + (w - 1) as f64,
+            self.offset_y + h as f64,
+        ));
 
-        return point_list;
+        point_list
     }
 
-    fn get_pixel(&self, data: &Vec<u8>, idx: i32) -> u8 {
+    fn get_pixel(&self, data: &[u8], idx: i32) -> u8 {
         match data.get(idx as usize) {
             Some(value) => *value,
             None => 0,
         }
     }
 
-    fn step(&self, idx: i32, data: &Vec<u8>, width: i32) -> i32 {
+    fn step(&self, idx: i32, data: &[u8], width: i32) -> i32 {
         let up_left = 0 < self.get_pixel(data, idx + 1);
         let up_right = 0 < self.get_pixel(data, idx + 2);
         let down_left = 0 < self.get_pixel(data, idx + width + 1);
@@ -164,6 +166,59 @@ impl MarchingSquares {
 
         state_inner.set(new_state);
 
-        return new_state;
+        new_state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // width × height 的 RGBA 图，(left, top) 起的 size × size 方块不透明
+    fn square(width: i32, height: i32, left: i32, top: i32, size: i32) -> Vec<u8> {
+        let mut data = vec![0; (width * height * 4) as usize];
+
+        for h in top..top + size {
+            for w in left..left + size {
+                let index = ((h * width + w) * 4) as usize;
+
+                data[index..index + 4].copy_from_slice(&[255, 255, 255, 255]);
+            }
+        }
+
+        data
+    }
+
+    // 最后一点回到起点：x 用 offset_x，y 用 offset_y
+    #[test]
+    fn outline_closes_at_start() {
+        let data = square(6, 6, 2, 1, 3);
+        let points = MarchingSquares::new(100.0, 7.0).get(&data, 6, 6);
+        let first = points.first().unwrap();
+        let last = points.last().unwrap();
+
+        assert!(points.len() > 4);
+        assert_eq!(first, last);
+        assert_eq!(*first, Pos::new(101.0, 8.0));
+    }
+
+    #[test]
+    fn outline_follows_offset() {
+        let data = square(6, 6, 2, 1, 3);
+        let points = MarchingSquares::new(0.0, 0.0).get(&data, 6, 6);
+        let moved = MarchingSquares::new(100.0, 7.0).get(&data, 6, 6);
+
+        assert_eq!(points.len(), moved.len());
+        assert!(points
+            .iter()
+            .zip(&moved)
+            .all(|(a, b)| b.left - a.left == 100.0 && b.top - a.top == 7.0));
+    }
+
+    #[test]
+    fn transparent_image_has_no_outline() {
+        let data = vec![0; 6 * 6 * 4];
+
+        assert!(MarchingSquares::new(0.0, 0.0).get(&data, 6, 6).is_empty());
     }
 }
@@ -1,7 +1,6 @@
 use std::fmt;
 
-use serde::Deserialize;
-use wasm_bindgen::JsValue;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum Interface {
@@ -33,228 +32,74 @@ pub enum Interface {
     SelectCardButton,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Plant {
-    Blover,
-    BoomWallNut,
-    Cabbagepult,
-    Cactus,
-    Catttail,
-    CherryBomb,
-    Chomper,
-    CobCannon,
-    CoffeeBean,
-    DoomShroom,
-    FlowerPot,
-    FumeShroom,
-    Garlic,
-    GatlingPea,
-    GloomShroom,
-    GoldMagnet,
-    GraveBuster,
-    HugeWallNut,
-    HypnoShroom,
-    IceShroom,
-    Imitators,
-    Jalapeno,
-    Kernelpult,
-    LilyPad,
-    MagnetShroom,
-    Marigold,
-    Melonpult,
-    Peashooter,
-    Plantern,
-    PotatoMine,
-    PuffShroom,
-    PumpkinHead,
-    Repeater,
-    ScaredyShroom,
-    SeaShroom,
-    SnowPea,
-    Spikerock,
-    Spikeweed,
-    SplitPea,
-    Squash,
-    Starfruit,
-    SunFlower,
-    SunFlower1,
-    SunShroom,
-    TallNut,
-    TangleKlep,
-    Threepeater,
-    Torchwood,
-    TwinSunflower,
-    UmbrellaLeaf,
-    WallNut,
-    WinterMelonpult,
-    PB100,
-    PB00,
-}
+impl Interface {
+    // 未知的名称返回 None 而不是错误，因此不实现 FromStr
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(name: &str) -> Option<Interface> {
+        let interface = match name {
+            "SelectorAdventureButton" => Interface::SelectorAdventureButton,
+            "SelectorBackground" => Interface::SelectorBackground,
+            "SelectorAdventureShadow" => Interface::SelectorAdventureShadow,
+            "SelectorSurvivalShadow" => Interface::SelectorSurvivalShadow,
+            "SelectorSurvivalButton" => Interface::SelectorSurvivalButton,
+            "SelectorChallengeShadow" => Interface::SelectorChallengeShadow,
+            "SelectorChallengeButton" => Interface::SelectorChallengeButton,
+            "SelectorZombieHand" => Interface::SelectorZombieHand,
+            "Sun" => Interface::Sun,
+            "SelectorWoodSign1" => Interface::SelectorWoodSign1,
+            "SelectorWoodSign2" => Interface::SelectorWoodSign2,
+            "SelectorWoodSign3" => Interface::SelectorWoodSign3,
+            "Background1" => Interface::Background1,
+            "Button" => Interface::Button,
+            "ShovelBack" => Interface::ShovelBack,
+            "Shovel" => Interface::Shovel,
+            "LawnCleaner" => Interface::LawnCleaner,
+            "PrepareGrowPlants" => Interface::PrepareGrowPlants,
+            "SeedChooserBackground" => Interface::SeedChooserBackground,
+            "SunBack" => Interface::SunBack,
+            "FlagMeterEmpty" => Interface::FlagMeterEmpty,
+            "FlagMeterFull" => Interface::FlagMeterFull,
+            "FlagMeterParts1" => Interface::FlagMeterParts1,
+            "FlagMeterParts2" => Interface::FlagMeterParts2,
+            "FlagMeterLevelProgress" => Interface::FlagMeterLevelProgress,
+            "SelectCardButton" => Interface::SelectCardButton,
+            _ => return None,
+        };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Zombie {
-    BackupDancer,
-    Balloonzombie,
-    BucketheadZombie,
-    Bungeezombie,
-    Catapult,
-    ConeheadZombie,
-    DancingZombie,
-    Diggerzombie,
-    DolphinRiderZombie,
-    Dr,
-    DuckyTubeZombie1,
-    FlagZombie,
-    FootballZombie,
-    Gargantuar,
-    Imp,
-    JackboxZombie,
-    LadderZombie,
-    NewspaperZombie,
-    PoleVaultingZombie,
-    ScreenDoorZombie,
-    SnorkelZombie,
-    Yeti,
-    Zombie,
-    ZombieBobsledTeam,
-    ZombiesJump,
-    Zomboni,
-    Zombie1,
+        Some(interface)
+    }
 }
 
+// 植物、僵尸由所在的精灵表决定，新增种类只需要数据，不需要新的枚举值
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SpriteType {
     Interface(Interface),
-    Plant(Plant),
-    Zombie(Zombie),
+    // 卡片与子弹同为植物类型
+    Plant,
+    Zombie,
     Nil,
 }
 
 impl SpriteType {
-    pub fn from_str(name: &str) -> SpriteType {
-        return match name {
-            // Interface
-            "SelectorAdventureButton" => SpriteType::Interface(Interface::SelectorAdventureButton),
-            "SelectorBackground" => SpriteType::Interface(Interface::SelectorBackground),
-            "SelectorAdventureShadow" => SpriteType::Interface(Interface::SelectorAdventureShadow),
-            "SelectorSurvivalShadow" => SpriteType::Interface(Interface::SelectorSurvivalShadow),
-            "SelectorSurvivalButton" => SpriteType::Interface(Interface::SelectorSurvivalButton),
-            "SelectorChallengeShadow" => SpriteType::Interface(Interface::SelectorChallengeShadow),
-            "SelectorChallengeButton" => SpriteType::Interface(Interface::SelectorChallengeButton),
-            "SelectorZombieHand" => SpriteType::Interface(Interface::SelectorZombieHand),
-            "Sun" => SpriteType::Interface(Interface::Sun),
-            "SelectorWoodSign1" => SpriteType::Interface(Interface::SelectorWoodSign1),
-            "SelectorWoodSign2" => SpriteType::Interface(Interface::SelectorWoodSign2),
-            "SelectorWoodSign3" => SpriteType::Interface(Interface::SelectorWoodSign3),
-            "Background1" => SpriteType::Interface(Interface::Background1),
-            "Button" => SpriteType::Interface(Interface::Button),
-            "ShovelBack" => SpriteType::Interface(Interface::ShovelBack),
-            "Shovel" => SpriteType::Interface(Interface::Shovel),
-            "LawnCleaner" => SpriteType::Interface(Interface::LawnCleaner),
-            "PrepareGrowPlants" => SpriteType::Interface(Interface::PrepareGrowPlants),
-            "SeedChooserBackground" => SpriteType::Interface(Interface::SeedChooserBackground),
-            "SunBack" => SpriteType::Interface(Interface::SunBack),
-            "FlagMeterEmpty" => SpriteType::Interface(Interface::FlagMeterEmpty),
-            "FlagMeterFull" => SpriteType::Interface(Interface::FlagMeterFull),
-            "FlagMeterParts1" => SpriteType::Interface(Interface::FlagMeterParts1),
-            "FlagMeterParts2" => SpriteType::Interface(Interface::FlagMeterParts2),
-            "FlagMeterLevelProgress" => SpriteType::Interface(Interface::FlagMeterLevelProgress),
-            "SelectCardButton" => SpriteType::Interface(Interface::SelectCardButton),
-            // Plant
-            "Blover" => SpriteType::Plant(Plant::Blover),
-            "BoomWallNut" => SpriteType::Plant(Plant::BoomWallNut),
-            "Cabbagepult" => SpriteType::Plant(Plant::Cabbagepult),
-            "Cactus" => SpriteType::Plant(Plant::Cactus),
-            "Catttail" => SpriteType::Plant(Plant::Catttail),
-            "CherryBomb" => SpriteType::Plant(Plant::CherryBomb),
-            "Chomper" => SpriteType::Plant(Plant::Chomper),
-            "CobCannon" => SpriteType::Plant(Plant::CobCannon),
-            "CoffeeBean" => SpriteType::Plant(Plant::CoffeeBean),
-            "DoomShroom" => SpriteType::Plant(Plant::DoomShroom),
-            "FlowerPot" => SpriteType::Plant(Plant::FlowerPot),
-            "FumeShroom" => SpriteType::Plant(Plant::FumeShroom),
-            "Garlic" => SpriteType::Plant(Plant::Garlic),
-            "GatlingPea" => SpriteType::Plant(Plant::GatlingPea),
-            "GloomShroom" => SpriteType::Plant(Plant::GloomShroom),
-            "GoldMagnet" => SpriteType::Plant(Plant::GoldMagnet),
-            "GraveBuster" => SpriteType::Plant(Plant::GraveBuster),
-            "HugeWallNut" => SpriteType::Plant(Plant::HugeWallNut),
-            "HypnoShroom" => SpriteType::Plant(Plant::HypnoShroom),
-            "IceShroom" => SpriteType::Plant(Plant::IceShroom),
-            "Imitators" => SpriteType::Plant(Plant::Imitators),
-            "Jalapeno" => SpriteType::Plant(Plant::Jalapeno),
-            "Kernelpult" => SpriteType::Plant(Plant::Kernelpult),
-            "LilyPad" => SpriteType::Plant(Plant::LilyPad),
-            "MagnetShroom" => SpriteType::Plant(Plant::MagnetShroom),
-            "Marigold" => SpriteType::Plant(Plant::Marigold),
-            "Melonpult" => SpriteType::Plant(Plant::Melonpult),
-            "Peashooter" => SpriteType::Plant(Plant::Peashooter),
-            "Plantern" => SpriteType::Plant(Plant::Plantern),
-            "PotatoMine" => SpriteType::Plant(Plant::PotatoMine),
-            "PuffShroom" => SpriteType::Plant(Plant::PuffShroom),
-            "PumpkinHead" => SpriteType::Plant(Plant::PumpkinHead),
-            "Repeater" => SpriteType::Plant(Plant::Repeater),
-            "ScaredyShroom" => SpriteType::Plant(Plant::ScaredyShroom),
-            "SeaShroom" => SpriteType::Plant(Plant::SeaShroom),
-            "SnowPea" => SpriteType::Plant(Plant::SnowPea),
-            "Spikerock" => SpriteType::Plant(Plant::Spikerock),
-            "Spikeweed" => SpriteType::Plant(Plant::Spikeweed),
-            "SplitPea" => SpriteType::Plant(Plant::SplitPea),
-            "Squash" => SpriteType::Plant(Plant::Squash),
-            "Starfruit" => SpriteType::Plant(Plant::Starfruit),
-            "SunFlower" => SpriteType::Plant(Plant::SunFlower),
-            "SunFlower1" => SpriteType::Plant(Plant::SunFlower1),
-            "SunShroom" => SpriteType::Plant(Plant::SunShroom),
-            "TallNut" => SpriteType::Plant(Plant::TallNut),
-            "TangleKlep" => SpriteType::Plant(Plant::TangleKlep),
-            "Threepeater" => SpriteType::Plant(Plant::Threepeater),
-            "Torchwood" => SpriteType::Plant(Plant::Torchwood),
-            "TwinSunflower" => SpriteType::Plant(Plant::TwinSunflower),
-            "UmbrellaLeaf" => SpriteType::Plant(Plant::UmbrellaLeaf),
-            "WallNut" => SpriteType::Plant(Plant::WallNut),
-            "WinterMelonpult" => SpriteType::Plant(Plant::WinterMelonpult),
-            "PB100" => SpriteType::Plant(Plant::PB100),
-            "PB00" => SpriteType::Plant(Plant::PB00),
-            // Zombie
-            "BackupDancer" => SpriteType::Zombie(Zombie::BackupDancer),
-            "Balloonzombie" => SpriteType::Zombie(Zombie::Balloonzombie),
-            "BucketheadZombie" => SpriteType::Zombie(Zombie::BucketheadZombie),
-            "Bungeezombie" => SpriteType::Zombie(Zombie::Bungeezombie),
-            "Catapult" => SpriteType::Zombie(Zombie::Catapult),
-            "ConeheadZombie" => SpriteType::Zombie(Zombie::ConeheadZombie),
-            "DancingZombie" => SpriteType::Zombie(Zombie::DancingZombie),
-            "Diggerzombie" => SpriteType::Zombie(Zombie::Diggerzombie),
-            "DolphinRiderZombie" => SpriteType::Zombie(Zombie::DolphinRiderZombie),
-            "Dr" => SpriteType::Zombie(Zombie::Dr),
-            "DuckyTubeZombie1" => SpriteType::Zombie(Zombie::DuckyTubeZombie1),
-            "FlagZombie" => SpriteType::Zombie(Zombie::FlagZombie),
-            "FootballZombie" => SpriteType::Zombie(Zombie::FootballZombie),
-            "Gargantuar" => SpriteType::Zombie(Zombie::Gargantuar),
-            "Imp" => SpriteType::Zombie(Zombie::Imp),
-            "JackboxZombie" => SpriteType::Zombie(Zombie::JackboxZombie),
-            "LadderZombie" => SpriteType::Zombie(Zombie::LadderZombie),
-            "NewspaperZombie" => SpriteType::Zombie(Zombie::NewspaperZombie),
-            "PoleVaultingZombie" => SpriteType::Zombie(Zombie::PoleVaultingZombie),
-            "ScreenDoorZombie" => SpriteType::Zombie(Zombie::ScreenDoorZombie),
-            "SnorkelZombie" => SpriteType::Zombie(Zombie::SnorkelZombie),
-            "Yeti" => SpriteType::Zombie(Zombie::Yeti),
-            "Zombie" => SpriteType::Zombie(Zombie::Zombie),
-            "ZombieBobsledTeam" => SpriteType::Zombie(Zombie::ZombieBobsledTeam),
-            "ZombiesJump" => SpriteType::Zombie(Zombie::ZombiesJump),
-            "Zomboni" => SpriteType::Zombie(Zombie::Zomboni),
-            "Zombie1" => SpriteType::Zombie(Zombie::Zombie1),
+    pub fn new(sheet_name: &str, name: &str) -> SpriteType {
+        match sheet_name {
+            "interface" => Interface::from_str(name).map_or(SpriteType::Nil, SpriteType::Interface),
+            "plant" | "card" => SpriteType::Plant,
+            "zombie" => SpriteType::Zombie,
             _ => SpriteType::Nil,
-        };
+        }
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum Event {
     Mousedown,
     Mousemove,
     Mouseup,
     Mouseleave,
     Mouseenter,
+    // 按键不直接派发，由 Keymap 转成 Action 后记录
+    Keydown,
 }
 
 impl fmt::Display for Event {
@@ -273,6 +118,9 @@ pub struct LevelData {
     pub plant_cards: Vec<String>,
     pub plants: Vec<LocInfo>,
     pub zombies: Vec<LocInfo>,
+    // 固定关卡的随机种子，未指定时由 Game 决定
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 impl LevelData {
@@ -283,19 +131,15 @@ impl LevelData {
         }
     }
 
-    pub fn new_from_json(json: &JsValue) -> Vec<LevelData> {
-        json.into_serde().unwrap()
+    pub fn new_from_json(json: &str) -> Result<Vec<LevelData>, serde_json::Error> {
+        serde_json::from_str(json)
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct State {
-    pub home_ready: bool,
-    pub preparing: bool,
-    pub over: bool,
-    pub paused: bool,
-    pub in_home_scene: bool,
-    pub selected_card: bool,
+    pub zombies_spawned: bool,
+    pub guideline: bool,
     pub max_sun: usize,
     pub cur_sun: usize,
 }
@@ -303,19 +147,20 @@ pub struct State {
 impl State {
     pub fn new() -> State {
         State {
-            in_home_scene: true,
+            guideline: true,
             max_sun: 10,
             ..Default::default()
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum Callback {
     ShowReady,
     TurnToLevelPrepareScene,
     ChoosePlantCard,
     BattleSetup,
+    PrepareBattle,
     ResetCardSelect,
     SelectPlantSeed,
     StartBattle,
@@ -0,0 +1,147 @@
+use crate::model::Event;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerPhase {
+    Down,
+    Move,
+    Up,
+    Cancel,
+    Enter,
+    Leave,
+}
+
+/* Pointers */
+// 鼠标、触摸、手写笔统一按指针处理，第一个按下的指针驱动拖动，其余手指忽略
+#[derive(Debug, Default)]
+pub struct Pointers {
+    active: Option<i32>,
+}
+
+impl Pointers {
+    pub fn new() -> Pointers {
+        Default::default()
+    }
+
+    pub fn active(&self) -> Option<i32> {
+        self.active
+    }
+
+    // 转换成原有的鼠标事件；Cancel 由调用方取消拖动，返回 None
+    pub fn translate(&mut self, phase: PointerPhase, id: i32) -> Option<Event> {
+        let is_active = self.active == Some(id);
+
+        match phase {
+            PointerPhase::Down if self.active.is_none() => {
+                self.active = Some(id);
+
+                Some(Event::Mousedown)
+            }
+            PointerPhase::Up if is_active => {
+                self.active = None;
+
+                Some(Event::Mouseup)
+            }
+            PointerPhase::Cancel if is_active => {
+                self.active = None;
+
+                None
+            }
+            // 没有按下时的移动来自悬停的鼠标
+            PointerPhase::Move if is_active || self.active.is_none() => Some(Event::Mousemove),
+            PointerPhase::Enter if is_active || self.active.is_none() => Some(Event::Mouseenter),
+            PointerPhase::Leave if is_active || self.active.is_none() => Some(Event::Mouseleave),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use PointerPhase::*;
+
+    fn translate(pointers: &mut Pointers, inputs: &[(PointerPhase, i32)]) -> Vec<Option<Event>> {
+        inputs
+            .iter()
+            .map(|(phase, id)| pointers.translate(*phase, *id))
+            .collect()
+    }
+
+    // 第一个按下的指针驱动拖动，第二根手指的按下、移动、抬起都被忽略
+    #[test]
+    fn second_pointer_is_ignored() {
+        let mut pointers = Pointers::new();
+        let events = translate(
+            &mut pointers,
+            &[(Down, 1), (Down, 2), (Move, 2), (Move, 1), (Up, 2), (Up, 1)],
+        );
+
+        assert_eq!(
+            events,
+            vec![
+                Some(Event::Mousedown),
+                None,
+                None,
+                Some(Event::Mousemove),
+                None,
+                Some(Event::Mouseup),
+            ]
+        );
+        assert_eq!(pointers.active(), None);
+    }
+
+    // 抬起后另一个指针可以接管
+    #[test]
+    fn next_pointer_takes_over_after_up() {
+        let mut pointers = Pointers::new();
+        let events = translate(&mut pointers, &[(Down, 1), (Up, 1), (Down, 2), (Move, 2)]);
+
+        assert_eq!(
+            events,
+            vec![
+                Some(Event::Mousedown),
+                Some(Event::Mouseup),
+                Some(Event::Mousedown),
+                Some(Event::Mousemove),
+            ]
+        );
+        assert_eq!(pointers.active(), Some(2));
+    }
+
+    #[test]
+    fn hover_moves_without_down() {
+        let mut pointers = Pointers::new();
+
+        assert_eq!(pointers.translate(Move, 1), Some(Event::Mousemove));
+        assert_eq!(pointers.active(), None);
+    }
+
+    // 取消不产生鼠标事件，只释放驱动拖动的指针
+    #[test]
+    fn cancel_releases_the_active_pointer() {
+        let mut pointers = Pointers::new();
+
+        pointers.translate(Down, 1);
+
+        assert_eq!(pointers.translate(Cancel, 2), None);
+        assert_eq!(pointers.active(), Some(1));
+        assert_eq!(pointers.translate(Cancel, 1), None);
+        assert_eq!(pointers.active(), None);
+        assert_eq!(pointers.translate(Up, 1), None);
+    }
+
+    #[test]
+    fn leave_is_sent_only_for_the_active_pointer() {
+        let mut pointers = Pointers::new();
+
+        assert_eq!(pointers.translate(Enter, 1), Some(Event::Mouseenter));
+        assert_eq!(pointers.translate(Leave, 1), Some(Event::Mouseleave));
+
+        pointers.translate(Down, 1);
+
+        assert_eq!(pointers.translate(Leave, 2), None);
+        assert_eq!(pointers.translate(Leave, 1), Some(Event::Mouseleave));
+        assert_eq!(pointers.active(), Some(1));
+    }
+}
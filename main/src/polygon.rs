@@ -0,0 +1,196 @@
+use crate::sprites::model::Pos;
+
+pub struct Polygon {}
+
+impl Polygon {
+    // 奇偶规则射线法，与绘制无关
+    pub fn contains(points: &[Pos], pos: &Pos) -> bool {
+        let mut inside = false;
+        let mut j = points.len().wrapping_sub(1);
+
+        for (i, a) in points.iter().enumerate() {
+            let b = &points[j];
+
+            if (a.top > pos.top) != (b.top > pos.top)
+                && pos.left < (b.left - a.left) * (pos.top - a.top) / (b.top - a.top) + a.left
+            {
+                inside = !inside;
+            }
+
+            j = i;
+        }
+
+        inside
+    }
+
+    // 点到线段 ab 的距离；a、b 重合时为到该点的距离，闭合轮廓首尾相同也能处理
+    fn distance(pos: &Pos, a: &Pos, b: &Pos) -> f64 {
+        let (dx, dy) = (b.left - a.left, b.top - a.top);
+        let length = dx * dx + dy * dy;
+        let t = match length > 0.0 {
+            true => (((pos.left - a.left) * dx + (pos.top - a.top) * dy) / length).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+        let (x, y) = (a.left + t * dx - pos.left, a.top + t * dy - pos.top);
+
+        (x * x + y * y).sqrt()
+    }
+
+    // Douglas-Peucker：去掉与保留的折线偏差不超过 epsilon 的点，保留首尾
+    pub fn simplify(points: &[Pos], epsilon: f64) -> Vec<Pos> {
+        if points.len() < 3 {
+            return points.to_vec();
+        }
+
+        let mut keep = vec![false; points.len()];
+        let mut ranges = vec![(0, points.len() - 1)];
+
+        keep[0] = true;
+        keep[points.len() - 1] = true;
+
+        while let Some((start, end)) = ranges.pop() {
+            let (mut index, mut farthest) = (start, 0.0);
+
+            for i in start + 1..end {
+                let distance = Polygon::distance(&points[i], &points[start], &points[end]);
+
+                if distance > farthest {
+                    index = i;
+                    farthest = distance;
+                }
+            }
+
+            if farthest > epsilon {
+                keep[index] = true;
+                ranges.push((start, index));
+                ranges.push((index, end));
+            }
+        }
+
+        points
+            .iter()
+            .zip(keep)
+            .filter(|(_, keep)| *keep)
+            .map(|(point, _)| *point)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coords: &[(f64, f64)]) -> Vec<Pos> {
+        coords
+            .iter()
+            .map(|(left, top)| Pos::new(*left, *top))
+            .collect()
+    }
+
+    fn contains(polygon: &[Pos], left: f64, top: f64) -> bool {
+        Polygon::contains(polygon, &Pos::new(left, top))
+    }
+
+    // 开口向下的 U 形
+    fn concave() -> Vec<Pos> {
+        points(&[
+            (0.0, 0.0),
+            (30.0, 0.0),
+            (30.0, 30.0),
+            (20.0, 30.0),
+            (20.0, 10.0),
+            (10.0, 10.0),
+            (10.0, 30.0),
+            (0.0, 30.0),
+        ])
+    }
+
+    #[test]
+    fn contains_concave_shape() {
+        let polygon = concave();
+
+        assert!(contains(&polygon, 5.0, 20.0));
+        assert!(contains(&polygon, 25.0, 20.0));
+        assert!(contains(&polygon, 15.0, 5.0));
+        // 凹口内、外接矩形外
+        assert!(!contains(&polygon, 15.0, 20.0));
+        assert!(!contains(&polygon, 35.0, 5.0));
+        assert!(!contains(&polygon, 15.0, -1.0));
+    }
+
+    // 边上的点按半开区间：左、上边在内，右、下边在外，相邻的多边形不会同时命中
+    #[test]
+    fn edge_points_are_half_open() {
+        let polygon = points(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]);
+
+        assert!(contains(&polygon, 0.0, 5.0));
+        assert!(contains(&polygon, 5.0, 0.0));
+        assert!(contains(&polygon, 0.0, 0.0));
+        assert!(!contains(&polygon, 10.0, 5.0));
+        assert!(!contains(&polygon, 5.0, 10.0));
+        assert!(!contains(&polygon, 10.0, 10.0));
+
+        // 凹口的边
+        let polygon = concave();
+
+        assert!(contains(&polygon, 15.0, 9.9));
+        assert!(!contains(&polygon, 15.0, 10.0));
+        assert!(contains(&polygon, 20.0, 20.0));
+        assert!(!contains(&polygon, 10.0, 20.0));
+    }
+
+    #[test]
+    fn degenerate_polygons_contain_nothing() {
+        assert!(!contains(&[], 0.0, 0.0));
+        assert!(!contains(&points(&[(0.0, 0.0), (10.0, 10.0)]), 5.0, 5.0));
+    }
+
+    #[test]
+    fn simplify_drops_collinear_points() {
+        let line = points(&[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 0.0)]);
+
+        assert_eq!(
+            Polygon::simplify(&line, 0.5),
+            points(&[(0.0, 0.0), (3.0, 0.0)])
+        );
+    }
+
+    // 偏差不超过 epsilon 的点去掉，超过的保留
+    #[test]
+    fn simplify_respects_tolerance() {
+        let bend = points(&[(0.0, 0.0), (5.0, 0.5), (10.0, 0.0)]);
+
+        assert_eq!(Polygon::simplify(&bend, 1.0).len(), 2);
+        assert_eq!(Polygon::simplify(&bend, 0.5).len(), 2);
+        assert_eq!(Polygon::simplify(&bend, 0.25), bend);
+    }
+
+    // 闭合轮廓首尾相同，首尾与拐角都要保留
+    #[test]
+    fn simplify_keeps_endpoints() {
+        let outline = points(&[
+            (0.0, 0.0),
+            (5.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 5.0),
+            (10.0, 10.0),
+            (5.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 5.0),
+            (0.0, 0.0),
+        ]);
+        let simplified = Polygon::simplify(&outline, 1.0);
+
+        assert_eq!(
+            simplified,
+            points(&[
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0)
+            ])
+        );
+        assert_eq!(Polygon::simplify(&outline[..2], 1.0), outline[..2].to_vec());
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use crate::{
+    builder::Builder,
+    model::{Interface, SpriteType},
+    snapshot::SpriteSnapshot,
+    sprites::{
+        model::{SpriteData, SpriteOrigin},
+        sprite_wrap::SpriteWrap,
+    },
+};
+
+// 每种精灵最多保留的空闲数量
+const MAX_FREE: usize = 64;
+
+/* SpritePool */
+// 子弹、阳光被回收后放回池中，再次创建时按首次构建时的状态重置，
+// 不再重新复制帧数据、创建绘制器和行为
+pub struct SpritePool {
+    free: HashMap<String, Vec<SpriteWrap>>,
+    templates: HashMap<String, SpriteSnapshot>,
+}
+
+impl Default for SpritePool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpritePool {
+    pub fn new() -> SpritePool {
+        SpritePool {
+            free: HashMap::new(),
+            templates: HashMap::new(),
+        }
+    }
+
+    fn key(origin: &SpriteOrigin) -> String {
+        format!("{}/{}", origin.sheet, origin.name)
+    }
+
+    // 创建、回收频繁的精灵：带 Projectile 组件的子弹，以及阳光
+    pub fn is_pooled(origin: &SpriteOrigin) -> bool {
+        origin.data.projectile.is_some()
+            || SpriteType::new(&origin.sheet, &origin.name) == SpriteType::Interface(Interface::Sun)
+    }
+
+    // data 的位置只取第一个
+    pub fn acquire(
+        &mut self,
+        builder: &Builder,
+        sheet_name: &str,
+        name: &str,
+        data: SpriteData,
+    ) -> SpriteWrap {
+        let key = format!("{}/{}", sheet_name, name);
+        let reused = self.free.get_mut(&key).and_then(|free| free.pop());
+
+        return match (reused, self.templates.get(&key)) {
+            (Some(mut sprite), Some(template)) => {
+                sprite.reset(template, SpriteOrigin::new(sheet_name, name, data));
+                sprite
+            }
+            _ => {
+                let sprite = builder
+                    .create_sprite(sheet_name, name, Some(data))
+                    .remove(0);
+
+                self.templates.entry(key).or_insert_with(|| sprite.save());
+
+                sprite
+            }
+        };
+    }
+
+    pub fn release(&mut self, sprite: SpriteWrap) {
+        let key = match &sprite.origin {
+            Some(origin) if SpritePool::is_pooled(origin) => SpritePool::key(origin),
+            _ => return,
+        };
+        let free = self.free.entry(key).or_default();
+
+        if free.len() < MAX_FREE {
+            free.push(sprite);
+        }
+    }
+
+    pub fn free_count(&self) -> usize {
+        self.free.values().map(|free| free.len()).sum()
+    }
+}
@@ -0,0 +1,278 @@
+use std::any::Any;
+
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement};
+
+use crate::{
+    data::{CANVAS_HEIGHT, CANVAS_WIDTH},
+    sprites::model::{Pos, SpriteCell},
+    util::window,
+    viewport::Viewport,
+};
+
+use super::{Image, Renderer};
+
+pub struct CanvasRenderer {
+    pub canvas: HtmlCanvasElement,
+    pub context: CanvasRenderingContext2d,
+    viewport: Viewport,
+    // 离屏画布，缓存期间与 context 互换
+    cache: Option<(HtmlCanvasElement, CanvasRenderingContext2d)>,
+    caching: bool,
+}
+
+impl CanvasRenderer {
+    pub fn new(canvas: HtmlCanvasElement) -> CanvasRenderer {
+        let context = CanvasRenderer::get_canvas_context(&canvas);
+
+        CanvasRenderer {
+            canvas,
+            context,
+            viewport: Viewport::default(),
+            cache: None,
+            caching: false,
+        }
+    }
+
+    pub fn create_canvas(width: u32, height: u32) -> HtmlCanvasElement {
+        let document = window().document().unwrap();
+        let canvas = document
+            .create_element("canvas")
+            .unwrap()
+            .dyn_into::<HtmlCanvasElement>()
+            .unwrap();
+
+        canvas.set_width(width);
+        canvas.set_height(height);
+        // 触摸拖动时不让浏览器滚动、缩放页面
+        canvas.set_attribute("style", "touch-action: none").unwrap();
+
+        canvas
+    }
+
+    pub fn get_canvas_context(canvas: &HtmlCanvasElement) -> CanvasRenderingContext2d {
+        canvas
+            .get_context("2d")
+            .unwrap()
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()
+            .unwrap()
+    }
+
+    pub fn execute_draw_image(
+        context: &CanvasRenderingContext2d,
+        image: &HtmlImageElement,
+        pos: &Pos,
+        cell: &SpriteCell,
+        scale: f64,
+    ) {
+        context
+            .draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
+                image,
+                cell.left,
+                cell.top,
+                cell.width,
+                cell.height,
+                pos.left,
+                pos.top,
+                cell.width * scale,
+                cell.height * scale,
+            )
+            .unwrap();
+    }
+
+    // 设置缩放、居中的变换并裁剪到逻辑区域，黑边区域不会被绘制
+    fn apply_viewport(context: &CanvasRenderingContext2d, viewport: &Viewport) {
+        let scale = viewport.scale * viewport.pixel_ratio;
+        let offset = viewport.to_physical(&Pos::new(0.0, 0.0));
+
+        context
+            .set_transform(scale, 0.0, 0.0, scale, offset.left, offset.top)
+            .unwrap();
+        context.begin_path();
+        context.rect(0.0, 0.0, CANVAS_WIDTH as f64, CANVAS_HEIGHT as f64);
+        context.clip();
+    }
+
+    fn trace_polygon(&self, points: &[Pos]) {
+        self.context.begin_path();
+
+        if let Some(first) = points.first() {
+            self.context.move_to(first.left, first.top);
+        }
+
+        points
+            .iter()
+            .skip(1)
+            .for_each(|point| self.context.line_to(point.left, point.top));
+
+        self.context.close_path();
+    }
+}
+
+impl Image for HtmlImageElement {
+    fn width(&self) -> f64 {
+        self.natural_width() as f64
+    }
+
+    fn height(&self) -> f64 {
+        self.natural_height() as f64
+    }
+
+    fn read_pixels(&self, cell: &SpriteCell) -> Option<Vec<u8>> {
+        let width = cell.width as u32;
+        let height = cell.height as u32;
+        let offscreen_canvas = CanvasRenderer::create_canvas(width, height);
+        let offscreen_context = CanvasRenderer::get_canvas_context(&offscreen_canvas);
+        let pos = Pos::new(0.0, 0.0);
+
+        CanvasRenderer::execute_draw_image(&offscreen_context, self, &pos, cell, 1.0);
+
+        offscreen_context
+            .get_image_data(0.0, 0.0, width as f64, height as f64)
+            .ok()
+            .map(|image_data| image_data.data().to_vec())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Renderer for CanvasRenderer {
+    // 绘制始终使用逻辑尺寸，由 resize 设置的变换映射到画布像素
+    fn width(&self) -> f64 {
+        CANVAS_WIDTH as f64
+    }
+
+    fn height(&self) -> f64 {
+        CANVAS_HEIGHT as f64
+    }
+
+    fn clear_rect(&mut self, left: f64, top: f64, width: f64, height: f64) {
+        self.context.clear_rect(left, top, width, height);
+    }
+
+    fn save(&mut self) {
+        self.context.save();
+    }
+
+    fn restore(&mut self) {
+        self.context.restore();
+    }
+
+    fn translate(&mut self, x: f64, y: f64) {
+        self.context.translate(x, y).unwrap();
+    }
+
+    fn draw_image(&mut self, image: &dyn Image, cell: &SpriteCell, pos: &Pos, scale: f64) {
+        if let Some(image) = image.as_any().downcast_ref::<HtmlImageElement>() {
+            CanvasRenderer::execute_draw_image(&self.context, image, pos, cell, scale);
+        }
+    }
+
+    fn set_stroke_style(&mut self, color: &str) {
+        self.context.set_stroke_style_str(color);
+    }
+
+    fn set_fill_style(&mut self, color: &str) {
+        self.context.set_fill_style_str(color);
+    }
+
+    fn stroke_rect(&mut self, left: f64, top: f64, width: f64, height: f64) {
+        self.context.stroke_rect(left, top, width, height);
+    }
+
+    fn fill_rect(&mut self, left: f64, top: f64, width: f64, height: f64) {
+        self.context.fill_rect(left, top, width, height);
+    }
+
+    fn stroke_line(&mut self, from: &Pos, to: &Pos) {
+        self.trace_polygon(&[*from, *to]);
+        self.context.stroke();
+    }
+
+    fn stroke_polygon(&mut self, points: &[Pos]) {
+        self.trace_polygon(points);
+        self.context.stroke();
+    }
+
+    fn set_font(&mut self, font: &str) {
+        self.context.set_font(font);
+    }
+
+    fn fill_text(&mut self, text: &str, left: f64, top: f64) {
+        self.context.fill_text(text, left, top).unwrap();
+    }
+
+    // 修改画布尺寸会重置绘图状态，需要重新设置变换与裁剪；离屏画布随之重建
+    fn resize(&mut self, viewport: &Viewport) {
+        let (width, height) = viewport.backing_size();
+
+        self.end_cache();
+        self.cache = None;
+        self.canvas.set_width(width);
+        self.canvas.set_height(height);
+        self.canvas
+            .set_attribute(
+                "style",
+                &format!(
+                    "touch-action: none; width: {}px; height: {}px",
+                    viewport.width, viewport.height
+                ),
+            )
+            .unwrap();
+
+        CanvasRenderer::apply_viewport(&self.context, viewport);
+
+        self.viewport = *viewport;
+    }
+
+    fn can_cache(&self) -> bool {
+        true
+    }
+
+    fn begin_cache(&mut self) {
+        if self.caching {
+            return;
+        }
+
+        let (canvas, context) = self.cache.take().unwrap_or_else(|| {
+            let (width, height) = self.viewport.backing_size();
+            let canvas = CanvasRenderer::create_canvas(width, height);
+            let context = CanvasRenderer::get_canvas_context(&canvas);
+
+            CanvasRenderer::apply_viewport(&context, &self.viewport);
+
+            (canvas, context)
+        });
+
+        context.clear_rect(0.0, 0.0, CANVAS_WIDTH as f64, CANVAS_HEIGHT as f64);
+
+        let context = std::mem::replace(&mut self.context, context);
+
+        self.cache = Some((canvas, context));
+        self.caching = true;
+    }
+
+    fn end_cache(&mut self) {
+        if let (true, Some((_, context))) = (self.caching, &mut self.cache) {
+            std::mem::swap(&mut self.context, context);
+            self.caching = false;
+        }
+    }
+
+    // 离屏画布与画布的像素尺寸相同，按像素贴回
+    fn draw_cache(&mut self) {
+        if let (false, Some((canvas, _))) = (self.caching, &self.cache) {
+            self.context.save();
+            self.context
+                .set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+                .unwrap();
+            self.context
+                .draw_image_with_html_canvas_element(canvas, 0.0, 0.0)
+                .unwrap();
+            self.context.restore();
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::sprites::model::{Pos, SpriteCell};
+
+use super::{Image, Renderer};
+
+// 无画布环境（原生测试、CI）使用，所有绘制都被丢弃
+pub struct HeadlessRenderer {
+    width: f64,
+    height: f64,
+}
+
+impl HeadlessRenderer {
+    pub fn new(width: u32, height: u32) -> HeadlessRenderer {
+        HeadlessRenderer {
+            width: width as f64,
+            height: height as f64,
+        }
+    }
+}
+
+impl Renderer for HeadlessRenderer {
+    fn width(&self) -> f64 {
+        self.width
+    }
+
+    fn height(&self) -> f64 {
+        self.height
+    }
+
+    fn clear_rect(&mut self, _left: f64, _top: f64, _width: f64, _height: f64) {}
+
+    fn save(&mut self) {}
+
+    fn restore(&mut self) {}
+
+    fn translate(&mut self, _x: f64, _y: f64) {}
+
+    fn draw_image(&mut self, _image: &dyn Image, _cell: &SpriteCell, _pos: &Pos, _scale: f64) {}
+
+    fn set_stroke_style(&mut self, _color: &str) {}
+
+    fn set_fill_style(&mut self, _color: &str) {}
+
+    fn stroke_rect(&mut self, _left: f64, _top: f64, _width: f64, _height: f64) {}
+
+    fn fill_rect(&mut self, _left: f64, _top: f64, _width: f64, _height: f64) {}
+
+    fn stroke_line(&mut self, _from: &Pos, _to: &Pos) {}
+
+    fn stroke_polygon(&mut self, _points: &[Pos]) {}
+
+    fn set_font(&mut self, _font: &str) {}
+
+    fn fill_text(&mut self, _text: &str, _left: f64, _top: f64) {}
+}
@@ -0,0 +1,79 @@
+use std::any::Any;
+
+use crate::{
+    sprites::model::{Pos, SpriteCell},
+    viewport::Viewport,
+};
+
+pub mod canvas;
+pub mod headless;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod software;
+
+/* *************** trait *************** */
+
+pub trait Image {
+    fn width(&self) -> f64;
+
+    fn height(&self) -> f64;
+
+    // 读取 cell 区域的 RGBA 像素，用于生成轮廓点
+    fn read_pixels(&self, _cell: &SpriteCell) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn as_any(&self) -> &dyn Any;
+}
+
+pub trait Renderer {
+    fn width(&self) -> f64;
+
+    fn height(&self) -> f64;
+
+    fn clear(&mut self) {
+        let (width, height) = (self.width(), self.height());
+
+        self.clear_rect(0.0, 0.0, width, height);
+    }
+
+    fn clear_rect(&mut self, left: f64, top: f64, width: f64, height: f64);
+
+    fn save(&mut self);
+
+    fn restore(&mut self);
+
+    fn translate(&mut self, x: f64, y: f64);
+
+    fn draw_image(&mut self, image: &dyn Image, cell: &SpriteCell, pos: &Pos, scale: f64);
+
+    fn set_stroke_style(&mut self, color: &str);
+
+    fn set_fill_style(&mut self, color: &str);
+
+    fn stroke_rect(&mut self, left: f64, top: f64, width: f64, height: f64);
+
+    fn fill_rect(&mut self, left: f64, top: f64, width: f64, height: f64);
+
+    fn stroke_line(&mut self, from: &Pos, to: &Pos);
+
+    fn stroke_polygon(&mut self, points: &[Pos]);
+
+    fn set_font(&mut self, font: &str);
+
+    fn fill_text(&mut self, text: &str, left: f64, top: f64);
+
+    // 画布尺寸或设备像素比变化时调用，之后仍按逻辑坐标绘制
+    fn resize(&mut self, _viewport: &Viewport) {}
+
+    // 离屏缓存：begin_cache 与 end_cache 之间的绘制写入缓存表面，
+    // 之后每帧用 draw_cache 整块贴回；不支持时由调用方直接绘制
+    fn can_cache(&self) -> bool {
+        false
+    }
+
+    fn begin_cache(&mut self) {}
+
+    fn end_cache(&mut self) {}
+
+    fn draw_cache(&mut self) {}
+}
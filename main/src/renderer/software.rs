@@ -0,0 +1,479 @@
+use std::{any::Any, cell::RefCell, fs::File, io, io::BufWriter, path::Path, rc::Rc};
+
+use crate::sprites::model::{Pos, SpriteCell};
+
+use super::{Image, Renderer};
+
+const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+// 5x7 点阵，每行低 5 位从左到右，仅覆盖阳光数字用到的字符
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+const GLYPHS: [(char, [u8; GLYPH_HEIGHT]); 11] = [
+    ('0', [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E]),
+    ('1', [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E]),
+    ('2', [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F]),
+    ('3', [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E]),
+    ('4', [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02]),
+    ('5', [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E]),
+    ('6', [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E]),
+    ('7', [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08]),
+    ('8', [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E]),
+    ('9', [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C]),
+    ('+', [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00]),
+];
+
+/* RgbaImage */
+// 解码后的精灵图或渲染结果，像素按 RGBA 逐行存放
+#[derive(Debug, Clone, PartialEq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    pub fn new(width: u32, height: u32) -> RgbaImage {
+        RgbaImage {
+            width,
+            height,
+            pixels: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    pub fn open(path: &Path) -> io::Result<RgbaImage> {
+        let mut decoder = png::Decoder::new(File::open(path)?);
+
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+
+        let mut reader = decoder.read_info().map_err(RgbaImage::to_io_error)?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut buf)
+            .map_err(RgbaImage::to_io_error)?;
+        let pixels = match info.color_type {
+            png::ColorType::Rgba => buf[..info.buffer_size()].to_vec(),
+            png::ColorType::Rgb => buf[..info.buffer_size()]
+                .chunks(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buf[..info.buffer_size()]
+                .chunks(2)
+                .flat_map(|ga| [ga[0], ga[0], ga[0], ga[1]])
+                .collect(),
+            _ => buf[..info.buffer_size()]
+                .iter()
+                .flat_map(|g| [*g, *g, *g, 255])
+                .collect(),
+        };
+
+        Ok(RgbaImage {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels))
+            .map_err(RgbaImage::to_io_error)
+    }
+
+    // 与基准图比较，返回任一通道差值超过 tolerance 的像素数
+    pub fn diff(&self, other: &RgbaImage, tolerance: u8) -> usize {
+        if self.width != other.width || self.height != other.height {
+            return (self.width.max(other.width) * self.height.max(other.height)) as usize;
+        }
+
+        self.pixels
+            .chunks(4)
+            .zip(other.pixels.chunks(4))
+            .filter(|(a, b)| {
+                a.iter()
+                    .zip(b.iter())
+                    .any(|(a, b)| a.abs_diff(*b) > tolerance)
+            })
+            .count()
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let index = ((y * self.width + x) * 4) as usize;
+
+        [
+            self.pixels[index],
+            self.pixels[index + 1],
+            self.pixels[index + 2],
+            self.pixels[index + 3],
+        ]
+    }
+
+    fn blend_pixel(&mut self, x: i64, y: i64, color: [u8; 4]) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 || color[3] == 0 {
+            return;
+        }
+
+        let index = ((y as u32 * self.width + x as u32) * 4) as usize;
+        let dst = &mut self.pixels[index..index + 4];
+        let alpha = color[3] as u32;
+        let inv = 255 - alpha;
+
+        for i in 0..3 {
+            dst[i] = ((color[i] as u32 * alpha + dst[i] as u32 * inv) / 255) as u8;
+        }
+
+        dst[3] = (alpha + dst[3] as u32 * inv / 255) as u8;
+    }
+
+    fn to_io_error<E: std::error::Error + Send + Sync + 'static>(e: E) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+impl Image for RgbaImage {
+    fn width(&self) -> f64 {
+        self.width as f64
+    }
+
+    fn height(&self) -> f64 {
+        self.height as f64
+    }
+
+    fn read_pixels(&self, cell: &SpriteCell) -> Option<Vec<u8>> {
+        let (left, top) = (cell.left as u32, cell.top as u32);
+        let (width, height) = (cell.width as u32, cell.height as u32);
+        let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+
+        for y in top..top + height {
+            for x in left..left + width {
+                match x < self.width && y < self.height {
+                    true => pixels.extend_from_slice(&self.get_pixel(x, y)),
+                    false => pixels.extend_from_slice(&[0, 0, 0, 0]),
+                }
+            }
+        }
+
+        Some(pixels)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/* SoftwareRenderer */
+#[derive(Debug, Clone)]
+struct RenderState {
+    translate: (f64, f64),
+    stroke: [u8; 4],
+    fill: [u8; 4],
+    font_size: f64,
+}
+
+impl Default for RenderState {
+    fn default() -> Self {
+        RenderState {
+            translate: (0.0, 0.0),
+            stroke: BLACK,
+            fill: BLACK,
+            font_size: 10.0,
+        }
+    }
+}
+
+// 纯 Rust 的 CPU 光栅化后端，用于在无浏览器环境下输出帧图像
+pub struct SoftwareRenderer {
+    frame: Rc<RefCell<RgbaImage>>,
+    state: RenderState,
+    stack: Vec<RenderState>,
+    // 缓存期间与帧互换，绘制写入缓存
+    cache: Option<RgbaImage>,
+    caching: bool,
+}
+
+impl SoftwareRenderer {
+    pub fn new(width: u32, height: u32) -> SoftwareRenderer {
+        SoftwareRenderer {
+            frame: Rc::new(RefCell::new(RgbaImage::new(width, height))),
+            state: Default::default(),
+            stack: vec![],
+            cache: None,
+            caching: false,
+        }
+    }
+
+    // 渲染器交给 Game 之后，通过该句柄读取帧
+    pub fn frame(&self) -> Rc<RefCell<RgbaImage>> {
+        Rc::clone(&self.frame)
+    }
+
+    pub fn parse_color(color: &str) -> [u8; 4] {
+        let hex = color.trim_start_matches('#');
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2).unwrap_or("00"), 16);
+
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Ok(r), Ok(g), Ok(b)) => [r, g, b, 255],
+            _ => BLACK,
+        }
+    }
+
+    fn parse_font_size(font: &str) -> f64 {
+        font.split("px")
+            .next()
+            .and_then(|size| size.trim().parse().ok())
+            .unwrap_or(10.0)
+    }
+
+    fn to_frame(&self, x: f64, y: f64) -> (f64, f64) {
+        (x + self.state.translate.0, y + self.state.translate.1)
+    }
+
+    fn draw_segment(&mut self, from: (f64, f64), to: (f64, f64)) {
+        let (mut x0, mut y0) = (from.0.floor() as i64, from.1.floor() as i64);
+        let (x1, y1) = (to.0.floor() as i64, to.1.floor() as i64);
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+        let mut err = dx + dy;
+        let color = self.state.stroke;
+        let mut frame = self.frame.borrow_mut();
+
+        loop {
+            frame.blend_pixel(x0, y0, color);
+
+            if x0 == x1 && y0 == y1 {
+                break;
+            }
+
+            let e2 = 2 * err;
+
+            if e2 >= dy {
+                err += dy;
+                x0 += sx;
+            }
+
+            if e2 <= dx {
+                err += dx;
+                y0 += sy;
+            }
+        }
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn width(&self) -> f64 {
+        self.frame.borrow().width as f64
+    }
+
+    fn height(&self) -> f64 {
+        self.frame.borrow().height as f64
+    }
+
+    fn clear_rect(&mut self, left: f64, top: f64, width: f64, height: f64) {
+        let (left, top) = self.to_frame(left, top);
+        let mut frame = self.frame.borrow_mut();
+        // 起点也要限制在画面内，否则最后一行会越过像素数组
+        let x0 = (left.max(0.0) as u32).min(frame.width);
+        let y0 = (top.max(0.0) as u32).min(frame.height);
+        let x1 = ((left + width).max(0.0) as u32).min(frame.width);
+        let y1 = ((top + height).max(0.0) as u32).min(frame.height);
+
+        for y in y0..y1 {
+            let begin = ((y * frame.width + x0) * 4) as usize;
+            let end = ((y * frame.width + x1.max(x0)) * 4) as usize;
+
+            frame.pixels[begin..end].fill(0);
+        }
+    }
+
+    fn save(&mut self) {
+        self.stack.push(self.state.clone());
+    }
+
+    fn restore(&mut self) {
+        if let Some(state) = self.stack.pop() {
+            self.state = state;
+        }
+    }
+
+    fn translate(&mut self, x: f64, y: f64) {
+        self.state.translate.0 += x;
+        self.state.translate.1 += y;
+    }
+
+    fn draw_image(&mut self, image: &dyn Image, cell: &SpriteCell, pos: &Pos, scale: f64) {
+        let image = match image.as_any().downcast_ref::<RgbaImage>() {
+            Some(image) => image,
+            None => return,
+        };
+
+        if scale <= 0.0 {
+            return;
+        }
+
+        let (left, top) = self.to_frame(pos.left, pos.top);
+        let (width, height) = (cell.width * scale, cell.height * scale);
+        let mut frame = self.frame.borrow_mut();
+        let x0 = left.round().max(0.0) as i64;
+        let y0 = top.round().max(0.0) as i64;
+        let x1 = ((left + width).round() as i64).min(frame.width as i64);
+        let y1 = ((top + height).round() as i64).min(frame.height as i64);
+
+        for y in y0..y1 {
+            let src_y = cell.top + ((y as f64 + 0.5 - top) / scale).floor();
+
+            if src_y < 0.0 || src_y >= image.height as f64 {
+                continue;
+            }
+
+            for x in x0..x1 {
+                let src_x = cell.left + ((x as f64 + 0.5 - left) / scale).floor();
+
+                if src_x < 0.0 || src_x >= image.width as f64 {
+                    continue;
+                }
+
+                frame.blend_pixel(x, y, image.get_pixel(src_x as u32, src_y as u32));
+            }
+        }
+    }
+
+    fn set_stroke_style(&mut self, color: &str) {
+        self.state.stroke = SoftwareRenderer::parse_color(color);
+    }
+
+    fn set_fill_style(&mut self, color: &str) {
+        self.state.fill = SoftwareRenderer::parse_color(color);
+    }
+
+    fn fill_rect(&mut self, left: f64, top: f64, width: f64, height: f64) {
+        let (left, top) = self.to_frame(left, top);
+        let color = self.state.fill;
+        let mut frame = self.frame.borrow_mut();
+
+        for y in top.round() as i64..(top + height).round() as i64 {
+            for x in left.round() as i64..(left + width).round() as i64 {
+                frame.blend_pixel(x, y, color);
+            }
+        }
+    }
+
+    fn stroke_rect(&mut self, left: f64, top: f64, width: f64, height: f64) {
+        let points = [
+            Pos::new(left, top),
+            Pos::new(left + width, top),
+            Pos::new(left + width, top + height),
+            Pos::new(left, top + height),
+        ];
+
+        self.stroke_polygon(&points);
+    }
+
+    fn stroke_line(&mut self, from: &Pos, to: &Pos) {
+        let from = self.to_frame(from.left, from.top);
+        let to = self.to_frame(to.left, to.top);
+
+        self.draw_segment(from, to);
+    }
+
+    fn stroke_polygon(&mut self, points: &[Pos]) {
+        for (i, point) in points.iter().enumerate() {
+            let next = &points[(i + 1) % points.len()];
+
+            self.stroke_line(point, next);
+        }
+    }
+
+    fn set_font(&mut self, font: &str) {
+        self.state.font_size = SoftwareRenderer::parse_font_size(font);
+    }
+
+    // 以基线为 top 绘制，与 canvas 默认的 alphabetic 基线一致
+    fn fill_text(&mut self, text: &str, left: f64, top: f64) {
+        let size = ((self.state.font_size / (GLYPH_HEIGHT + 1) as f64).floor() as i64).max(1);
+        let (left, top) = self.to_frame(left, top);
+        let (mut x, y) = (
+            left.round() as i64,
+            top.round() as i64 - size * GLYPH_HEIGHT as i64,
+        );
+        let color = self.state.fill;
+        let mut frame = self.frame.borrow_mut();
+
+        for ch in text.chars() {
+            if let Some((_, rows)) = GLYPHS.iter().find(|(glyph, _)| *glyph == ch) {
+                for (row, bits) in rows.iter().enumerate() {
+                    for col in 0..GLYPH_WIDTH {
+                        if bits & (1 << (GLYPH_WIDTH - 1 - col)) == 0 {
+                            continue;
+                        }
+
+                        for dy in 0..size {
+                            for dx in 0..size {
+                                frame.blend_pixel(
+                                    x + col as i64 * size + dx,
+                                    y + row as i64 * size + dy,
+                                    color,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+
+            x += (GLYPH_WIDTH as i64 + 1) * size;
+        }
+    }
+
+    fn can_cache(&self) -> bool {
+        true
+    }
+
+    fn begin_cache(&mut self) {
+        if self.caching {
+            return;
+        }
+
+        let (width, height) = (self.frame.borrow().width, self.frame.borrow().height);
+        let mut cache = match self.cache.take() {
+            Some(cache) if cache.width == width && cache.height == height => cache,
+            _ => RgbaImage::new(width, height),
+        };
+
+        cache.pixels.fill(0);
+        std::mem::swap(&mut *self.frame.borrow_mut(), &mut cache);
+
+        self.cache = Some(cache);
+        self.caching = true;
+    }
+
+    fn end_cache(&mut self) {
+        if let (true, Some(cache)) = (self.caching, &mut self.cache) {
+            std::mem::swap(&mut *self.frame.borrow_mut(), cache);
+            self.caching = false;
+        }
+    }
+
+    fn draw_cache(&mut self) {
+        let cache = match (self.caching, &self.cache) {
+            (false, Some(cache)) => cache,
+            _ => return,
+        };
+        let mut frame = self.frame.borrow_mut();
+
+        if frame.width != cache.width || frame.height != cache.height {
+            return;
+        }
+
+        for y in 0..cache.height {
+            for x in 0..cache.width {
+                frame.blend_pixel(x as i64, y as i64, cache.get_pixel(x, y));
+            }
+        }
+    }
+}
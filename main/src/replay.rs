@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{keymap::Action, model::Event, scene::model::SceneKind};
+
+/* InputRecord */
+// tick 为输入到达时已执行的逻辑帧数，回放时在同一逻辑帧之前派发
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InputRecord {
+    pub tick: u64,
+    pub event: Event,
+    pub x: f64,
+    pub y: f64,
+    // 按键操作，event 为 Keydown，x、y 为当时的指针位置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<Action>,
+}
+
+impl InputRecord {
+    pub fn new(tick: u64, event: Event, x: f64, y: f64) -> InputRecord {
+        InputRecord {
+            tick,
+            event,
+            x,
+            y,
+            action: None,
+        }
+    }
+
+    pub fn new_action(tick: u64, action: Action, x: f64, y: f64) -> InputRecord {
+        InputRecord {
+            tick,
+            event: Event::Keydown,
+            x,
+            y,
+            action: Some(action),
+        }
+    }
+}
+
+/* Replay */
+// 关卡、种子与输入序列足以在新的 Game 中复现整局
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Replay {
+    pub level: String,
+    pub seed: u64,
+    // 录制开始时进入的第一个场景，回放从同一场景开始
+    pub scene: SceneKind,
+    pub inputs: Vec<InputRecord>,
+    // 录制结束时的逻辑帧数，回放会执行到这里
+    #[serde(default)]
+    pub ticks: u64,
+}
+
+impl Replay {
+    pub fn new(level: &str, seed: u64, scene: SceneKind) -> Replay {
+        Replay {
+            level: level.to_string(),
+            seed,
+            scene,
+            inputs: vec![],
+            ticks: 0,
+        }
+    }
+
+    pub fn record(&mut self, input: InputRecord) {
+        self.ticks = self.ticks.max(input.tick);
+        self.inputs.push(input);
+    }
+
+    pub fn new_from_json(json: &str) -> serde_json::Result<Replay> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+// 游戏内所有随机数都来自 Game 持有的 Rng，相同种子得到相同的对局
+pub const DEFAULT_SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+/* Rng */
+// xorshift64，状态为 0 时序列全为 0，因此种子 0 换成默认种子
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rng {
+    seed: u64,
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        let state = match seed {
+            0 => DEFAULT_SEED,
+            _ => seed,
+        };
+
+        Rng { seed, state }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+
+        x
+    }
+
+    // [0, 1) 之间的浮点数
+    pub fn random(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn random_int(&mut self, min: f64, max: f64) -> f64 {
+        let min = min.ceil();
+        let max = max.floor();
+
+        (self.random() * (max - min)).floor() + min
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Rng::new(DEFAULT_SEED)
+    }
+}
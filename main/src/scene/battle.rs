@@ -0,0 +1,48 @@
+use crate::{
+    behavior::model::BehaviorType,
+    game::Game,
+    model::{Callback, Interface, SpriteType},
+};
+
+use super::model::{Scene, SceneKind, Transition};
+
+pub struct BattleScene;
+
+impl Scene for BattleScene {
+    fn enter(&self, game: &mut Game, from: Option<SceneKind>) {
+        match from {
+            // 背景移回草坪后摆放关卡界面
+            Some(SceneKind::SeedSelect) => {
+                Game::register_callback(
+                    &mut game.sprites,
+                    SpriteType::Interface(Interface::Background1),
+                    BehaviorType::Scroll,
+                    Callback::PrepareBattle,
+                );
+
+                game.toggle_behaviors(&[BehaviorType::Scroll], true);
+            }
+            // 跳过主页与选卡直接开始
+            _ => {
+                game.create_level_background();
+                game.prepare_battle();
+                game.create_plant_card_left();
+            }
+        }
+    }
+
+    // 被结算场景覆盖时不会退出，回到主页时才清空战场
+    fn exit(&self, game: &mut Game) {
+        game.end_battle();
+    }
+
+    fn update(&self, game: &mut Game) {
+        game.update_world(true);
+
+        if game.zombie_reached_house() {
+            game.transition(Transition::Push(SceneKind::Defeat));
+        } else if game.state.zombies_spawned && game.zombie_count() == 0 {
+            game.transition(Transition::Push(SceneKind::Victory));
+        }
+    }
+}
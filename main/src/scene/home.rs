@@ -1,10 +1,12 @@
-use crate::{builder::Builder, sprites::sprite_wrap::SpriteWrap};
+use crate::{builder::Builder, game::Game, sprites::sprite_wrap::SpriteWrap};
+
+use super::model::{Scene, SceneKind};
 
 pub struct HomeScene;
 
 impl HomeScene {
     pub fn create(builder: &Builder) -> Vec<SpriteWrap> {
-        let sprites = builder.from_names(
+        builder.from_names(
             "interface",
             vec![
                 "SelectorBackground",
@@ -19,8 +21,17 @@ impl HomeScene {
                 "SelectorChallengeButton",
                 "SelectorZombieHand",
             ],
-        );
+        )
+    }
+}
+
+// 点击冒险模式，僵尸手动画结束后进入关卡
+impl Scene for HomeScene {
+    fn enter(&self, game: &mut Game, _from: Option<SceneKind>) {
+        game.create_home_scene();
+    }
 
-        return sprites;
+    fn exit(&self, game: &mut Game) {
+        game.clear_sprites();
     }
 }
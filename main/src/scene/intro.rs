@@ -0,0 +1,13 @@
+use crate::{behavior::model::BehaviorType, game::Game};
+
+use super::model::{Scene, SceneKind};
+
+pub struct LevelIntroScene;
+
+// 背景移动到最右展示本关僵尸，结束后选卡
+impl Scene for LevelIntroScene {
+    fn enter(&self, game: &mut Game, _from: Option<SceneKind>) {
+        game.create_level_background();
+        game.toggle_behaviors(&[BehaviorType::Scroll], true);
+    }
+}
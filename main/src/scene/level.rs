@@ -2,8 +2,11 @@ use crate::{
     builder::Builder,
     loc::Loc,
     model::LocInfo,
-    sprites::{model::Pos, sprite_wrap::SpriteWrap},
-    util::get_random_int,
+    rng::Rng,
+    sprites::{
+        model::{Pos, SpriteData},
+        sprite_wrap::SpriteWrap,
+    },
 };
 
 pub struct LevelScene;
@@ -11,9 +14,8 @@ pub struct LevelScene;
 impl LevelScene {
     pub fn create(builder: &Builder, scenes: &[String]) -> Vec<SpriteWrap> {
         let scenes: Vec<&str> = scenes.iter().map(|s| s.as_ref()).collect();
-        let sprites = builder.from_names("interface", scenes);
 
-        return sprites;
+        builder.from_names("interface", scenes)
     }
 
     pub fn flag(builder: &Builder, flag_num: usize) -> Vec<SpriteWrap> {
@@ -31,28 +33,26 @@ impl LevelScene {
 
         sprites.extend(flags);
 
-        return sprites;
+        sprites
     }
     pub fn seed_chooser(builder: &Builder) -> Vec<SpriteWrap> {
-        let sprites = builder.from_names(
+        builder.from_names(
             "interface",
             vec!["SeedChooserBackground", "SunBack", "SelectCardButton"],
-        );
-
-        return sprites;
+        )
     }
 
-    pub fn plant_card(builder: &Builder, card_names: &Vec<String>) -> Vec<SpriteWrap> {
+    pub fn plant_card(builder: &Builder, card_names: &[String]) -> Vec<SpriteWrap> {
         let card_names: Vec<&str> = card_names.iter().map(|s| s.as_ref()).collect();
         let plant_pos = Loc::put_increase_y(0.0, 0.0, 60.0, card_names.len())
             .into_iter()
             .map(|pos| vec![pos])
             .collect();
 
-        return builder.from_names_and_poss("card", card_names, plant_pos);
+        builder.from_names_and_poss("card", card_names, plant_pos)
     }
 
-    pub fn plant_seed(builder: &Builder, card_names: &Vec<String>) -> Vec<SpriteWrap> {
+    pub fn plant_seed(builder: &Builder, card_names: &[String]) -> Vec<SpriteWrap> {
         let scale = 0.725;
         let card_names: Vec<&str> = card_names.iter().map(|s| s.as_ref()).collect();
         let plant_pos = Loc::put_increase_x(
@@ -66,26 +66,26 @@ impl LevelScene {
         .into_iter()
         .map(|pos| vec![pos])
         .collect();
-        let plants = builder.from_names_and_poss("card", card_names, plant_pos);
 
-        return plants;
+        builder.from_names_and_poss("card", card_names, plant_pos)
     }
 
-    pub fn plants(builder: &Builder, plants: &Vec<LocInfo>) -> Vec<SpriteWrap> {
-        return builder.create_plants(plants, true);
+    pub fn plants(builder: &Builder, plants: &[LocInfo]) -> Vec<SpriteWrap> {
+        builder.create_plants(plants, true)
     }
 
-    pub fn zombies(builder: &Builder, zombies: &Vec<LocInfo>) -> Vec<SpriteWrap> {
-        return builder.create_plants(zombies, false);
+    pub fn zombies(builder: &Builder, zombies: &[LocInfo]) -> Vec<SpriteWrap> {
+        builder.create_plants(zombies, false)
     }
 
-    pub fn drop_sun(builder: &Builder, pos: Option<&Pos>) -> Vec<SpriteWrap> {
+    // 阳光由对象池创建，这里只生成位置与下落距离
+    pub fn sun_data(builder: &Builder, rng: &mut Rng, pos: Option<&Pos>) -> SpriteData {
         let (pos, distance) = match pos {
             Some(pos) => (*pos, 50.0),
             None => {
-                let left = get_random_int(100.0, 800.0);
-                let distance = get_random_int(150.0, 550.0);
-                let top = get_random_int(0.0, 75.0);
+                let left = rng.random_int(100.0, 800.0);
+                let distance = rng.random_int(150.0, 550.0);
+                let top = rng.random_int(0.0, 75.0);
 
                 (Pos::new(left, -top), distance)
             }
@@ -96,8 +96,6 @@ impl LevelScene {
         sun_data.pos[0] = pos;
         sun_data.behaviors[2].distance = distance;
 
-        let sun = builder.create_sprite("interface", "Sun", Some(sun_data));
-
-        sun
+        sun_data
     }
 }
@@ -0,0 +1,97 @@
+use crate::{
+    data::{CANVAS_HEIGHT, CANVAS_WIDTH},
+    game::Game,
+    manifest::AssetError,
+};
+
+use super::model::Scene;
+
+// 最多显示的错误条数
+const MAX_ERRORS: usize = 12;
+
+/* LoadProgress */
+// 启动时逐个加载资源文件的进度与出错的文件
+#[derive(Debug, Default)]
+pub struct LoadProgress {
+    pub total: usize,
+    pub loaded: usize,
+    pub current: String,
+    pub errors: Vec<AssetError>,
+}
+
+impl LoadProgress {
+    pub fn new() -> LoadProgress {
+        Default::default()
+    }
+
+    pub fn start(&mut self, total: usize) {
+        self.total = total;
+        self.loaded = 0;
+        self.current.clear();
+        self.errors.clear();
+    }
+
+    pub fn finish(&mut self, path: &str, error: Option<AssetError>) {
+        self.loaded += 1;
+        self.current = path.to_string();
+        self.errors.extend(error);
+    }
+
+    pub fn fail(&mut self, error: AssetError) {
+        self.errors.push(error);
+    }
+
+    pub fn is_failed(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    pub fn ratio(&self) -> f64 {
+        match self.total {
+            0 => 0.0,
+            total => self.loaded as f64 / total as f64,
+        }
+    }
+}
+
+pub struct LoadingScene;
+
+// 资源加载完成前的场景，没有精灵也不执行逻辑帧；出错时停留在此显示错误
+impl Scene for LoadingScene {
+    fn update(&self, _game: &mut Game) {}
+
+    fn draw(&self, game: &mut Game) {
+        let renderer = game.renderer.as_mut();
+        let progress = &game.loading;
+        let (width, height) = (CANVAS_WIDTH as f64, CANVAS_HEIGHT as f64);
+        let (bar_width, bar_height) = (400.0, 16.0);
+        let (left, top) = ((width - bar_width) / 2.0, height / 2.0);
+
+        renderer.save();
+        renderer.set_fill_style("#000000");
+        renderer.fill_rect(0.0, 0.0, width, height);
+        renderer.set_font("16px 黑体");
+        renderer.set_stroke_style("#ffffff");
+        renderer.set_fill_style("#ffffff");
+        renderer.stroke_rect(left, top, bar_width, bar_height);
+        renderer.fill_rect(left, top, bar_width * progress.ratio(), bar_height);
+        renderer.fill_text(
+            &format!(
+                "{}/{} {}",
+                progress.loaded, progress.total, progress.current
+            ),
+            left,
+            top - 10.0,
+        );
+
+        if progress.is_failed() {
+            renderer.set_fill_style("#ff4040");
+            renderer.fill_text("资源加载失败：", 40.0, top + 50.0);
+
+            for (i, error) in progress.errors.iter().take(MAX_ERRORS).enumerate() {
+                renderer.fill_text(&error.to_string(), 40.0, top + 75.0 + i as f64 * 20.0);
+            }
+        }
+
+        renderer.restore();
+    }
+}
@@ -1,2 +1,9 @@
+pub mod battle;
 pub mod home;
+pub mod intro;
 pub mod level;
+pub mod loading;
+pub mod model;
+pub mod paused;
+pub mod result;
+pub mod seed_select;
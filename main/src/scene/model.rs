@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::Game;
+
+use super::{
+    battle::BattleScene,
+    home::HomeScene,
+    intro::LevelIntroScene,
+    loading::LoadingScene,
+    paused::PausedScene,
+    result::{DefeatScene, VictoryScene},
+    seed_select::SeedSelectScene,
+};
+
+/* SceneKind */
+// 场景栈中保存的场景标识，快照只需保存这份列表
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SceneKind {
+    Loading,
+    #[default]
+    Home,
+    LevelIntro,
+    SeedSelect,
+    Battle,
+    Paused,
+    Victory,
+    Defeat,
+}
+
+impl SceneKind {
+    pub fn scene(&self) -> &'static dyn Scene {
+        match self {
+            SceneKind::Loading => &LoadingScene,
+            SceneKind::Home => &HomeScene,
+            SceneKind::LevelIntro => &LevelIntroScene,
+            SceneKind::SeedSelect => &SeedSelectScene,
+            SceneKind::Battle => &BattleScene,
+            SceneKind::Paused => &PausedScene,
+            SceneKind::Victory => &VictoryScene,
+            SceneKind::Defeat => &DefeatScene,
+        }
+    }
+}
+
+/* Transition */
+// 由 Game::transition 排队，在逻辑帧末尾依次执行
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transition {
+    Push(SceneKind),
+    Pop,
+    Replace(SceneKind),
+}
+
+/* *************** trait *************** */
+
+// 场景本身不保存状态，精灵与数据都在 Game 上
+pub trait Scene {
+    // from 为被替换或被覆盖的场景，直接进入时为 None
+    fn enter(&self, _game: &mut Game, _from: Option<SceneKind>) {}
+
+    fn exit(&self, _game: &mut Game) {}
+
+    // 只有栈顶场景执行逻辑帧
+    fn update(&self, game: &mut Game) {
+        game.update_world(false);
+    }
+
+    // 精灵绘制完成后，栈内场景自底向上绘制
+    fn draw(&self, _game: &mut Game) {}
+}
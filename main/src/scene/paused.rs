@@ -0,0 +1,25 @@
+use crate::game::Game;
+
+use super::{
+    model::{Scene, SceneKind},
+    result::draw_banner,
+};
+
+pub struct PausedScene;
+
+// 覆盖在其他场景之上，由 Game::pause、Game::resume 进出
+impl Scene for PausedScene {
+    fn enter(&self, game: &mut Game, _from: Option<SceneKind>) {
+        game.freeze();
+    }
+
+    fn exit(&self, game: &mut Game) {
+        game.unfreeze();
+    }
+
+    fn update(&self, _game: &mut Game) {}
+
+    fn draw(&self, game: &mut Game) {
+        draw_banner(game, "暂停，点击继续");
+    }
+}
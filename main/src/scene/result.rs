@@ -0,0 +1,38 @@
+use crate::{
+    data::{CANVAS_HEIGHT, CANVAS_WIDTH},
+    game::Game,
+};
+
+use super::model::Scene;
+
+pub struct VictoryScene;
+
+pub struct DefeatScene;
+
+// 结算场景覆盖在战斗之上，战场停止更新；点击后由 Game::back_home 回到主页
+impl Scene for VictoryScene {
+    fn update(&self, _game: &mut Game) {}
+
+    fn draw(&self, game: &mut Game) {
+        draw_banner(game, "胜利！点击返回主页");
+    }
+}
+
+impl Scene for DefeatScene {
+    fn update(&self, _game: &mut Game) {}
+
+    fn draw(&self, game: &mut Game) {
+        draw_banner(game, "僵尸吃掉了你的脑子！点击返回主页");
+    }
+}
+
+// 画布中央的提示文字
+pub fn draw_banner(game: &mut Game, text: &str) {
+    let left = CANVAS_WIDTH as f64 / 2.0 - text.chars().count() as f64 * 24.0;
+    let top = CANVAS_HEIGHT as f64 / 2.0;
+
+    game.renderer.save();
+    game.renderer.set_font("48px 黑体");
+    game.renderer.fill_text(text, left, top);
+    game.renderer.restore();
+}
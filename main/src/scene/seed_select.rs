@@ -0,0 +1,16 @@
+use crate::game::Game;
+
+use super::model::{Scene, SceneKind};
+
+pub struct SeedSelectScene;
+
+// 点击开始按钮后收起选卡界面进入战斗
+impl Scene for SeedSelectScene {
+    fn enter(&self, game: &mut Game, _from: Option<SceneKind>) {
+        game.prepare_choose_card();
+    }
+
+    fn exit(&self, game: &mut Game) {
+        game.close_seed_chooser();
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    behavior::model::{BehaviorState, BehaviorType},
+    entity::{Entities, Entity},
+    model::{Callback, State},
+    rng::Rng,
+    scene::model::SceneKind,
+    sprites::{model::SpriteOrigin, sprite::SpriteState},
+    time_system::Speed,
+};
+
+/* SpriteSnapshot */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpriteSnapshot {
+    pub entity: Entity,
+    // 只有辅助线不是由 Builder 创建，为 None
+    pub origin: Option<SpriteOrigin>,
+    pub sprite: SpriteState,
+    pub behaviors: Vec<BehaviorState>,
+    pub callbacks: Vec<(BehaviorType, Callback)>,
+}
+
+/* Snapshot */
+// 逻辑帧之间的整局状态；鼠标等输入状态不保存，恢复后从下一次输入开始
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub level: String,
+    pub rng: Rng,
+    pub now: f64,
+    pub ticks: u64,
+    pub last_gc_time: f64,
+    pub last_sun_time: f64,
+    pub state: State,
+    pub speed: Speed,
+    pub scenes: Vec<SceneKind>,
+    pub sun: u32,
+    pub sun_produce_rate: f64,
+    pub entities: Entities,
+    pub seed_chooser: Vec<Entity>,
+    pub plant_cards: Vec<Entity>,
+    pub sprites: Vec<SpriteSnapshot>,
+}
+
+impl Snapshot {
+    pub fn new_from_json(json: &str) -> Result<Snapshot, SnapshotError> {
+        serde_json::from_str(json).map_err(SnapshotError::Json)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Json(serde_json::Error),
+    UnknownLevel(String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Json(e) => write!(f, "invalid snapshot: {}", e),
+            SnapshotError::UnknownLevel(name) => write!(f, "level {} not found", name),
+        }
+    }
+}
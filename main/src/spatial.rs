@@ -0,0 +1,306 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+
+use crate::{
+    data::{COL_X_COORD, ROW_Y_COORD},
+    entity::Entity,
+    loc::Loc,
+    sprites::{model::Pos, sprite_wrap::SpriteWrap},
+};
+
+pub const ROWS: usize = ROW_Y_COORD.len() - 1;
+
+pub const COLS: usize = COL_X_COORD.len() - 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Layer {
+    Plant,
+    Zombie,
+    Projectile,
+}
+
+const LAYERS: [Layer; 3] = [Layer::Plant, Layer::Zombie, Layer::Projectile];
+
+/* SpatialIndex */
+// 按图层、行、列分桶的精灵句柄，用于碰撞等查询的粗筛；
+// 每个逻辑帧同步一次，只有跨格的精灵才在桶之间移动
+#[derive(Debug)]
+pub struct SpatialIndex {
+    buckets: Vec<Vec<Entity>>,
+    cells: HashMap<Entity, usize>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpatialIndex {
+    pub fn new() -> SpatialIndex {
+        SpatialIndex {
+            buckets: vec![vec![]; LAYERS.len() * ROWS * COLS],
+            cells: HashMap::new(),
+        }
+    }
+
+    fn bucket(layer: Layer, row: usize, col: usize) -> usize {
+        let layer = LAYERS.iter().position(|item| *item == layer).unwrap();
+
+        (layer * ROWS + row) * COLS + col
+    }
+
+    // 草坪上的植物、僵尸按所在行列；子弹没有行列，按中心点所在的格子
+    fn locate(sprite: &SpriteWrap) -> Option<usize> {
+        if !sprite.is_visible() {
+            return None;
+        }
+
+        let (layer, (row, col)) = match &sprite.sprite {
+            inner if inner.projectile.is_some() => {
+                let pos = inner.transform.pos;
+                let cell = inner.artist.get_current_cell()?;
+                let center = Pos::new(pos.left + cell.width / 2.0, pos.top + cell.height / 2.0);
+
+                (Layer::Projectile, Loc::get_row_col_by_pos(&center))
+            }
+            inner if inner.lane.is_none() => return None,
+            _ if sprite.is_plant() => (Layer::Plant, sprite.get_loc()),
+            _ if sprite.is_zombie() => (Layer::Zombie, sprite.get_loc()),
+            _ => return None,
+        };
+
+        match row < ROWS && col < COLS {
+            true => Some(SpatialIndex::bucket(layer, row, col)),
+            false => None,
+        }
+    }
+
+    fn remove(&mut self, entity: Entity, bucket: usize) {
+        let entities = &mut self.buckets[bucket];
+
+        if let Some(index) = entities.iter().position(|item| *item == entity) {
+            entities.remove(index);
+        }
+    }
+
+    pub fn sync(&mut self, sprites: &Vec<SpriteWrap>) {
+        let mut seen = HashSet::with_capacity(sprites.len());
+
+        for sprite in sprites {
+            let entity = sprite.entity;
+            let bucket = SpatialIndex::locate(sprite);
+            let last_bucket = self.cells.get(&entity).copied();
+
+            seen.insert(entity);
+
+            if bucket == last_bucket {
+                continue;
+            }
+
+            if let Some(last_bucket) = last_bucket {
+                self.remove(entity, last_bucket);
+                self.cells.remove(&entity);
+            }
+
+            if let Some(bucket) = bucket {
+                self.buckets[bucket].push(entity);
+                self.cells.insert(entity, bucket);
+            }
+        }
+
+        // 已被移除的精灵
+        let removed: Vec<(Entity, usize)> = self
+            .cells
+            .iter()
+            .filter(|(entity, _)| !seen.contains(entity))
+            .map(|(entity, bucket)| (*entity, *bucket))
+            .collect();
+
+        for (entity, bucket) in removed {
+            self.remove(entity, bucket);
+            self.cells.remove(&entity);
+        }
+    }
+
+    pub fn query(
+        &self,
+        layer: Layer,
+        row: usize,
+        cols: RangeInclusive<usize>,
+    ) -> impl Iterator<Item = Entity> + '_ {
+        // 草坪以外的行没有格子
+        let cols = (row < ROWS).then(|| *cols.start()..=(*cols.end()).min(COLS - 1));
+
+        cols.into_iter()
+            .flatten()
+            .flat_map(move |col| self.buckets[SpatialIndex::bucket(layer, row, col)].iter())
+            .copied()
+    }
+
+    // x 超出草坪时取最近的列
+    fn col_by_x(x: f64) -> usize {
+        match x < COL_X_COORD[0] {
+            true => 0,
+            false => Loc::get_col_by_x(x).min(COLS - 1),
+        }
+    }
+
+    // 第 row 行从 x 所在格子向右，例如射手前方的僵尸
+    pub fn ahead(&self, layer: Layer, row: usize, x: f64) -> impl Iterator<Item = Entity> + '_ {
+        let cols = SpatialIndex::col_by_x(x)..=COLS - 1;
+
+        self.query(layer, row, cols)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        artists::artist::Artist,
+        model::SpriteType,
+        sprites::{
+            components::{Lane, Projectile, Transform},
+            model::{Offset, SpriteCell},
+            sprite::Sprite,
+        },
+    };
+
+    fn sprite(index: usize, name: SpriteType, pos: Pos, lane: Option<Lane>) -> SpriteWrap {
+        let artist = Artist::new(None, vec![SpriteCell::new(0.0, 0.0, 40.0, 40.0)], 1.0);
+        let transform = Transform::new(pos, Offset::default(), true, 0);
+        let mut sprite = Sprite::new(name, Box::new(artist), transform);
+
+        sprite.lane = lane;
+
+        let mut sprite = SpriteWrap::new(sprite, vec![]);
+
+        sprite.entity = Entity::new(index, 0);
+        sprite
+    }
+
+    fn plant(index: usize, row: usize, col: usize) -> SpriteWrap {
+        let name = SpriteType::Plant;
+
+        sprite(index, name, Pos::new(0.0, 0.0), Some(Lane::new(row, col)))
+    }
+
+    fn zombie(index: usize, row: usize, col: usize) -> SpriteWrap {
+        let name = SpriteType::Zombie;
+
+        sprite(index, name, Pos::new(0.0, 0.0), Some(Lane::new(row, col)))
+    }
+
+    fn query(index: &SpatialIndex, layer: Layer, row: usize, col: usize) -> Vec<Entity> {
+        index.query(layer, row, col..=col).collect()
+    }
+
+    #[test]
+    fn inserts_by_layer_row_and_col() {
+        let mut index = SpatialIndex::new();
+        let sprites = vec![plant(0, 2, 3), zombie(1, 2, 3)];
+
+        index.sync(&sprites);
+
+        assert_eq!(query(&index, Layer::Plant, 2, 3), vec![Entity::new(0, 0)]);
+        assert_eq!(query(&index, Layer::Zombie, 2, 3), vec![Entity::new(1, 0)]);
+        assert!(query(&index, Layer::Plant, 2, 4).is_empty());
+        assert!(query(&index, Layer::Plant, 1, 3).is_empty());
+        assert!(query(&index, Layer::Projectile, 2, 3).is_empty());
+    }
+
+    #[test]
+    fn moves_between_buckets() {
+        let mut index = SpatialIndex::new();
+        let mut sprites = vec![zombie(0, 1, 5), plant(1, 1, 2)];
+
+        index.sync(&sprites);
+        sprites[0].sprite.lane = Some(Lane::new(1, 4));
+        index.sync(&sprites);
+
+        assert!(query(&index, Layer::Zombie, 1, 5).is_empty());
+        assert_eq!(query(&index, Layer::Zombie, 1, 4), vec![Entity::new(0, 0)]);
+
+        // 隐藏与移除的精灵都不再出现
+        sprites[0].sprite.toggle();
+        index.sync(&sprites);
+        assert!(query(&index, Layer::Zombie, 1, 4).is_empty());
+
+        sprites.pop();
+        index.sync(&sprites);
+        assert!(query(&index, Layer::Plant, 1, 2).is_empty());
+    }
+
+    #[test]
+    fn queries_at_lawn_edges() {
+        let mut index = SpatialIndex::new();
+        let sprites = vec![
+            zombie(0, 0, 0),
+            zombie(1, ROWS - 1, COLS - 1),
+            zombie(2, ROWS, 0),
+            zombie(3, 0, COLS),
+        ];
+
+        index.sync(&sprites);
+
+        assert_eq!(query(&index, Layer::Zombie, 0, 0), vec![Entity::new(0, 0)]);
+        assert_eq!(
+            query(&index, Layer::Zombie, ROWS - 1, COLS - 1),
+            vec![Entity::new(1, 0)]
+        );
+        // 草坪外的行列不建索引，越界的查询为空或截断到最后一列
+        assert!(query(&index, Layer::Zombie, ROWS, 0).is_empty());
+        assert_eq!(
+            index
+                .query(Layer::Zombie, ROWS - 1, COLS - 2..=COLS + 5)
+                .collect::<Vec<_>>(),
+            vec![Entity::new(1, 0)]
+        );
+        assert_eq!(
+            index.query(Layer::Zombie, 0, 0..=COLS).collect::<Vec<_>>(),
+            vec![Entity::new(0, 0)]
+        );
+    }
+
+    #[test]
+    fn ahead_looks_right_of_x() {
+        let mut index = SpatialIndex::new();
+        let sprites = vec![zombie(0, 2, 0), zombie(1, 2, COLS - 1)];
+
+        index.sync(&sprites);
+
+        // 草坪左侧以外从第一列开始，右侧以外只剩最后一列
+        assert_eq!(index.ahead(Layer::Zombie, 2, 0.0).count(), 2);
+        assert_eq!(
+            index
+                .ahead(Layer::Zombie, 2, COL_X_COORD[1])
+                .collect::<Vec<_>>(),
+            vec![Entity::new(1, 0)]
+        );
+        assert_eq!(
+            index.ahead(Layer::Zombie, 2, 10_000.0).collect::<Vec<_>>(),
+            vec![Entity::new(1, 0)]
+        );
+        assert!(index.ahead(Layer::Zombie, 3, 0.0).next().is_none());
+        assert!(index.ahead(Layer::Zombie, ROWS, 0.0).next().is_none());
+    }
+
+    // 子弹没有行列，按中心点所在的格子
+    #[test]
+    fn projectiles_use_their_center() {
+        let mut index = SpatialIndex::new();
+        let name = SpriteType::Plant;
+        let pos = Pos::new(COL_X_COORD[3] - 10.0, ROW_Y_COORD[1] - 10.0);
+        let mut bullet = sprite(0, name, pos, None);
+
+        bullet.sprite.projectile = Some(Projectile { attack: 20.0 });
+        index.sync(&vec![bullet]);
+
+        assert_eq!(
+            query(&index, Layer::Projectile, 1, 3),
+            vec![Entity::new(0, 0)]
+        );
+        assert!(query(&index, Layer::Plant, 1, 3).is_empty());
+    }
+}
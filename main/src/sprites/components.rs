@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+use super::model::{CollisionMargin, Offset, Pos};
+
+/* Transform */
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Transform {
+    pub pos: Pos,
+    pub offset: Offset,
+    pub visible: bool,
+    pub order: usize,
+}
+
+impl Transform {
+    pub fn new(pos: Pos, offset: Offset, visible: bool, order: usize) -> Transform {
+        Transform {
+            pos,
+            offset,
+            visible,
+            order,
+        }
+    }
+}
+
+/* Lane */
+// 草坪上的行列，植物放置时确定，僵尸随行走更新
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Lane {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Lane {
+    pub fn new(row: usize, col: usize) -> Lane {
+        Lane { row, col }
+    }
+}
+
+/* Animation */
+// 按 duration 逐帧切换；有 interval 时播放到最后一帧后停留 interval 再从头播放
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Animation {
+    pub duration: f64,
+    pub interval: Option<f64>,
+    pub last_advance: f64,
+    pub playing: bool,
+}
+
+impl Animation {
+    pub fn new(duration: f64, interval: Option<f64>) -> Animation {
+        Animation {
+            duration,
+            interval,
+            last_advance: 0.0,
+            playing: false,
+        }
+    }
+}
+
+/* Health */
+// attack 为每秒造成的伤害，life 降到 0 时精灵被移除
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Health {
+    pub life: f64,
+    pub attack: f64,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Health {
+            life: 100.0,
+            attack: 1.0,
+        }
+    }
+}
+
+/* Collider */
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct Collider {
+    pub margin: CollisionMargin,
+    pub collided: bool,
+}
+
+impl Collider {
+    pub fn new(margin: CollisionMargin) -> Collider {
+        Collider {
+            margin,
+            collided: false,
+        }
+    }
+}
+
+/* Clickable */
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct Clickable {
+    pub clicked: bool,
+}
+
+/* Projectile */
+// 击中僵尸时造成 attack 点伤害
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Projectile {
+    #[serde(default = "default_attack")]
+    pub attack: f64,
+}
+
+fn default_attack() -> f64 {
+    1.0
+}
+
+/* Shooter */
+// 间隔触发时发射 projectile 指定的子弹
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Shooter {
+    pub projectile: String,
+    #[serde(default)]
+    pub ready: bool,
+}
+
+/* SunProducer */
+// 切换动画结束后在 offset 处产出阳光
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SunProducer {
+    #[serde(default)]
+    pub offset: Offset,
+    #[serde(default)]
+    pub ready: bool,
+}
+
+/* SwitchState */
+// 请求 SwitchBehavior 切换到第 index 组动画
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SwitchState {
+    pub active: bool,
+    pub index: usize,
+}
+
+impl SwitchState {
+    pub fn new(active: bool, index: usize) -> SwitchState {
+        SwitchState { active, index }
+    }
+}
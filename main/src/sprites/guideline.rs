@@ -1,47 +1,39 @@
-use web_sys::CanvasRenderingContext2d;
-
 use crate::{
     artists::model::{Draw, Resource},
     data::{COL_X_COORD, ROW_Y_COORD},
+    model::SpriteType,
+    renderer::Renderer,
 };
 
 use super::{
-    model::{CollisionMargin, DrawInfo, Offset, Pos, Update},
+    components::Transform,
+    model::{CollisionMargin, Pos},
+    sprite::Sprite,
     sprite_wrap::SpriteWrap,
 };
 
 pub struct GuidelineArtist {}
 
+impl Default for GuidelineArtist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GuidelineArtist {
     pub fn new() -> GuidelineArtist {
         GuidelineArtist {}
     }
 
-    fn draw_row(context: &CanvasRenderingContext2d) {
+    fn draw_row(renderer: &mut dyn Renderer) {
         ROW_Y_COORD.iter().for_each(|row| {
-            context.begin_path();
-
-            context.move_to(0.5, *row + 0.5);
-            context.line_to(1400.5, *row + 0.5);
-
-            context.close_path();
-            context.stroke();
-
-            context.close_path();
+            renderer.stroke_line(&Pos::new(0.5, *row + 0.5), &Pos::new(1400.5, *row + 0.5));
         });
     }
 
-    fn draw_col(context: &CanvasRenderingContext2d) {
+    fn draw_col(renderer: &mut dyn Renderer) {
         COL_X_COORD.iter().for_each(|col| {
-            context.begin_path();
-
-            context.move_to(*col + 0.5, 0.5);
-            context.line_to(*col + 0.5, 600.5);
-
-            context.close_path();
-            context.stroke();
-
-            context.close_path();
+            renderer.stroke_line(&Pos::new(*col + 0.5, 0.5), &Pos::new(*col + 0.5, 600.5));
         });
     }
 }
@@ -49,67 +41,28 @@ impl GuidelineArtist {
 impl Resource for GuidelineArtist {}
 
 impl Draw for GuidelineArtist {
-    fn draw(
-        &self,
-        context: &CanvasRenderingContext2d,
-        _pos: &Pos,
-        _collision_margin: &CollisionMargin,
-    ) {
-        context.save();
+    fn draw(&self, renderer: &mut dyn Renderer, _pos: &Pos, _collision_margin: &CollisionMargin) {
+        renderer.save();
 
-        GuidelineArtist::draw_row(context);
-        GuidelineArtist::draw_col(context);
+        GuidelineArtist::draw_row(renderer);
+        GuidelineArtist::draw_col(renderer);
 
-        context.restore();
+        renderer.restore();
     }
 }
 
-pub struct Guideline {
-    pub draw_info: DrawInfo,
-    artist: Box<dyn Draw>,
-    collision_margin: CollisionMargin,
-}
+pub struct Guideline {}
 
 impl Guideline {
+    // 直接构造带辅助线绘制器的精灵
+    #[allow(clippy::new_ret_no_self)]
     pub fn new() -> SpriteWrap {
-        let guideline = Guideline {
-            draw_info: Default::default(),
-            artist: Box::new(GuidelineArtist::new()),
-            collision_margin: CollisionMargin::no_collision(),
-        };
-
-        return SpriteWrap::new(Box::new(guideline), vec![]);
-    }
-}
-
-impl Update for Guideline {
-    fn draw(&self, context: &CanvasRenderingContext2d) {
-        self.artist
-            .draw(context, &self.draw_info.pos, &self.collision_margin);
-    }
-
-    fn get_artist(&mut self) -> &mut dyn Draw {
-        self.artist.as_mut()
-    }
-
-    fn get_draw_info(&self) -> Option<&DrawInfo> {
-        Some(&self.draw_info)
-    }
-
-    fn update_draw_info(&mut self, pos: Option<Pos>, offset: Option<Offset>) {
-        let pos = match pos {
-            Some(pos) => pos,
-            None => self.draw_info.pos,
-        };
-        let offset = match offset {
-            Some(offset) => offset,
-            None => self.draw_info.offset,
-        };
-
-        self.draw_info = DrawInfo::new(pos, offset, true, 1);
-    }
+        let sprite = Sprite::new(
+            SpriteType::Nil,
+            Box::new(GuidelineArtist::new()),
+            Transform::new(Default::default(), Default::default(), true, 0),
+        );
 
-    fn get_read_artist(&self) -> &dyn Draw {
-        self.artist.as_ref()
+        SpriteWrap::new(sprite, vec![])
     }
 }
@@ -0,0 +1,145 @@
+use std::cmp::Ordering;
+
+use crate::{
+    data::ROW_Y_COORD,
+    entity::Entity,
+    model::{Interface, SpriteType},
+};
+
+use super::sprite_wrap::SpriteWrap;
+
+/* RenderLayer */
+// 绘制图层，按声明顺序从下往上绘制
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RenderLayer {
+    Background,
+    Lawn,
+    Projectile,
+    Hud,
+    // 阳光会落在卡片栏、铲子上，收集时飞向阳光计数，放在 HUD 之上才不被遮住
+    Effect,
+    Overlay,
+}
+
+impl RenderLayer {
+    pub fn of(sprite: &SpriteWrap) -> RenderLayer {
+        if sprite.sprite.projectile.is_some() {
+            return RenderLayer::Projectile;
+        }
+
+        match sprite.name() {
+            SpriteType::Zombie => RenderLayer::Lawn,
+            // 卡片同为植物类型，但不在草坪上
+            SpriteType::Plant => match sprite.sprite.lane {
+                Some(_) => RenderLayer::Lawn,
+                None => RenderLayer::Hud,
+            },
+            SpriteType::Interface(interface) => match interface {
+                Interface::LawnCleaner => RenderLayer::Lawn,
+                Interface::Sun | Interface::PrepareGrowPlants => RenderLayer::Effect,
+                Interface::Button
+                | Interface::ShovelBack
+                | Interface::Shovel
+                | Interface::SunBack
+                | Interface::SeedChooserBackground
+                | Interface::SelectCardButton
+                | Interface::FlagMeterEmpty
+                | Interface::FlagMeterFull
+                | Interface::FlagMeterParts1
+                | Interface::FlagMeterParts2
+                | Interface::FlagMeterLevelProgress => RenderLayer::Hud,
+                _ => RenderLayer::Background,
+            },
+            // 辅助线
+            SpriteType::Nil => RenderLayer::Overlay,
+        }
+    }
+
+    // 草坪上按所在行的底边排序，下面一行遮住上面一行；不在行里的按自身底边
+    fn depth(sprite: &SpriteWrap) -> f64 {
+        if let Some(lane) = sprite.sprite.lane {
+            return ROW_Y_COORD[(lane.row + 1).min(ROW_Y_COORD.len() - 1)];
+        }
+
+        let height = match sprite.sprite.artist.get_current_cell() {
+            Some(cell) => cell.height,
+            None => 0.0,
+        };
+
+        sprite.get_transform().pos.top + height
+    }
+
+    // 先比较图层；草坪层内按深度，其余按 order，相同时保持加入顺序
+    pub fn compare(a: &SpriteWrap, b: &SpriteWrap) -> Ordering {
+        let layer = RenderLayer::of(a);
+
+        layer.cmp(&RenderLayer::of(b)).then_with(|| match layer {
+            RenderLayer::Lawn => RenderLayer::depth(a)
+                .partial_cmp(&RenderLayer::depth(b))
+                .unwrap_or(Ordering::Equal),
+            _ => a.get_order().cmp(&b.get_order()),
+        })
+    }
+}
+
+// 精灵、插值后的位置与当前帧
+type DrawKey = (Entity, [f64; 6]);
+
+/* LayerCache */
+// 静态图层的离屏缓存；记录上次绘制时各精灵的位置与帧，
+// 背景滚动、动画换帧或精灵增减时重绘
+#[derive(Debug, Default)]
+pub struct LayerCache {
+    key: Option<Vec<DrawKey>>,
+}
+
+impl LayerCache {
+    pub fn new() -> LayerCache {
+        LayerCache { key: None }
+    }
+
+    fn key(sprites: &[&SpriteWrap], alpha: f64) -> Vec<DrawKey> {
+        sprites
+            .iter()
+            .map(|sprite| {
+                let pos = sprite.get_transform().pos;
+                let (x, y) = sprite.draw_offset(alpha);
+                let cell = sprite
+                    .sprite
+                    .artist
+                    .get_current_cell()
+                    .copied()
+                    .unwrap_or_default();
+
+                (
+                    sprite.entity,
+                    [
+                        pos.left + x,
+                        pos.top + y,
+                        cell.left,
+                        cell.top,
+                        cell.width,
+                        cell.height,
+                    ],
+                )
+            })
+            .collect()
+    }
+
+    // 与上次绘制时不同则记录新的状态，返回是否需要重绘
+    pub fn refresh(&mut self, sprites: &[&SpriteWrap], alpha: f64) -> bool {
+        let key = LayerCache::key(sprites, alpha);
+
+        if self.key.as_ref() == Some(&key) {
+            return false;
+        }
+
+        self.key = Some(key);
+
+        true
+    }
+
+    pub fn invalidate(&mut self) {
+        self.key = None;
+    }
+}
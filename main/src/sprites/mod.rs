@@ -1,6 +1,6 @@
+pub mod components;
 pub mod guideline;
+pub mod layer;
 pub mod model;
-pub mod plants;
 pub mod sprite;
 pub mod sprite_wrap;
-pub mod zombies;
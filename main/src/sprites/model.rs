@@ -1,81 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use serde::Deserialize;
-use wasm_bindgen::JsValue;
-use web_sys::CanvasRenderingContext2d;
+use serde::{Deserialize, Serialize};
 
-use crate::{artists::model::Draw, behavior::model::BehaviorData, model::SpriteType};
+use crate::behavior::model::BehaviorData;
 
-/* *************** trait *************** */
-
-pub trait Update {
-    fn draw(&self, context: &CanvasRenderingContext2d);
-
-    fn get_artist(&mut self) -> &mut dyn Draw;
-
-    fn get_read_artist(&self) -> &dyn Draw;
-
-    fn get_draw_info(&self) -> Option<&DrawInfo> {
-        return None;
-    }
-
-    fn update_draw_info(&mut self, _pos: Option<Pos>, _offset: Option<Offset>) {}
-
-    fn name(&self) -> SpriteType {
-        SpriteType::Nil
-    }
-
-    fn toggle(&mut self) {}
-
-    fn is_visible(&self) -> bool {
-        return true;
-    }
-
-    fn get_order(&self) -> usize {
-        0
-    }
-
-    fn tirgger_switch(&mut self) -> (bool, usize) {
-        (false, 0)
-    }
-
-    fn check_collision(&self, _sprites: &Vec<&Box<dyn Update>>) -> bool {
-        false
-    }
-
-    fn is_collision(&self) -> bool {
-        false
-    }
-
-    fn update_loc(&mut self, _row: usize, _col: usize) {}
-
-    fn get_loc(&self) -> (usize, usize) {
-        (99, 99)
-    }
-
-    fn is_clicked(&self) -> bool {
-        false
-    }
-
-    fn set_clicked(&mut self, _clicked: bool) {}
-
-    fn get_gen_sun_flag(&self) -> bool {
-        false
-    }
-
-    fn set_gen_sun_flag(&mut self, _gened: bool) {}
-
-    fn get_shoot(&self) -> bool {
-        false
-    }
-
-    fn set_shoot(&mut self, _shoot: bool) {}
-}
+use super::components::{Health, Projectile, Shooter, SunProducer};
 
 /* *************** Struct *************** */
 
 /* CollisionMargin */
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 pub struct CollisionMargin {
     pub left: f64,
     pub top: f64,
@@ -115,7 +49,7 @@ impl Default for CollisionMargin {
 }
 
 /* Pos */
-#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct Pos {
     pub left: f64,
     pub top: f64,
@@ -125,6 +59,30 @@ impl Pos {
     pub fn new(left: f64, top: f64) -> Pos {
         Pos { left, top }
     }
+
+    // 预先计算的轮廓，键名同帧数据，点相对于第一帧的左上角
+    pub fn outlines_from_json(json: &str) -> Result<HashMap<String, Vec<Pos>>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    // 每个点一行，与帧数据文件的格式一致
+    pub fn write_outlines(outlines: &BTreeMap<String, Vec<Pos>>) -> String {
+        let groups: Vec<String> = outlines
+            .iter()
+            .map(|(name, points)| {
+                let lines: Vec<String> = points
+                    .iter()
+                    .map(|point| {
+                        format!("    {{ \"left\": {}, \"top\": {} }}", point.left, point.top)
+                    })
+                    .collect();
+
+                format!("  {:?}: [\n{}\n  ]", name, lines.join(",\n"))
+            })
+            .collect();
+
+        format!("{{\n{}\n}}\n", groups.join(",\n"))
+    }
 }
 
 impl PartialEq for Pos {
@@ -134,7 +92,7 @@ impl PartialEq for Pos {
 }
 
 /* Offset */
-#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct Offset {
     pub x: f64,
     pub y: f64,
@@ -159,13 +117,28 @@ impl Velocit {
     }
 }
 
+/* CellTrim */
+// 裁剪掉透明边的帧：x、y 为裁剪后的帧在原始帧中的偏移，width、height 为原始帧的大小
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CellTrim {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
 /* SpriteCell */
-#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct SpriteCell {
     pub left: f64,
     pub top: f64,
     pub width: f64,
     pub height: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trim: Option<CellTrim>,
+    // 锚点，相对于原始帧大小的比例
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pivot: Option<(f64, f64)>,
 }
 
 impl SpriteCell {
@@ -175,15 +148,19 @@ impl SpriteCell {
             top,
             width,
             height,
+            trim: None,
+            pivot: None,
         }
     }
 
-    pub fn new_from_json(json: &JsValue) -> HashMap<String, Vec<SpriteCell>> {
-        json.into_serde().unwrap()
+    pub fn new_from_json(
+        json: &str,
+    ) -> Result<HashMap<String, Vec<SpriteCell>>, serde_json::Error> {
+        serde_json::from_str(json)
     }
 }
 
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SpriteData {
     pub constructor: String,
     pub pos: Vec<Pos>,
@@ -198,6 +175,14 @@ pub struct SpriteData {
     pub order: usize,
     #[serde(default)]
     pub collision_margin: CollisionMargin,
+    #[serde(default)]
+    pub health: Option<Health>,
+    #[serde(default)]
+    pub projectile: Option<Projectile>,
+    #[serde(default)]
+    pub shooter: Option<Shooter>,
+    #[serde(default)]
+    pub sun_producer: Option<SunProducer>,
 }
 
 fn default_visible() -> bool {
@@ -219,29 +204,33 @@ impl SpriteData {
             scale: 1.0,
             order: 0,
             collision_margin: Default::default(),
+            health: None,
+            projectile: None,
+            shooter: None,
+            sun_producer: None,
         }
     }
 
-    pub fn new_from_json(json: &JsValue) -> HashMap<String, SpriteData> {
-        json.into_serde().unwrap()
+    pub fn new_from_json(json: &str) -> Result<HashMap<String, SpriteData>, serde_json::Error> {
+        serde_json::from_str(json)
     }
 }
 
-#[derive(Debug, Default, Clone, Deserialize)]
-pub struct DrawInfo {
-    pub pos: Pos,
-    pub offset: Offset,
-    pub visible: bool,
-    pub order: usize,
+/* SpriteOrigin */
+// 精灵由哪张精灵图的哪份数据创建，恢复快照时据此重新构建
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpriteOrigin {
+    pub sheet: String,
+    pub name: String,
+    pub data: SpriteData,
 }
 
-impl DrawInfo {
-    pub fn new(pos: Pos, offset: Offset, visible: bool, order: usize) -> DrawInfo {
-        DrawInfo {
-            pos,
-            offset,
-            visible,
-            order,
+impl SpriteOrigin {
+    pub fn new(sheet: &str, name: &str, data: SpriteData) -> SpriteOrigin {
+        SpriteOrigin {
+            sheet: sheet.to_string(),
+            name: name.to_string(),
+            data,
         }
     }
 }
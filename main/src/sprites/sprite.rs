@@ -1,127 +1,165 @@
-use web_sys::CanvasRenderingContext2d;
-
-use crate::{artists::model::Draw, model::SpriteType};
-
-use super::model::{CollisionMargin, DrawInfo, Offset, Pos, Update, Velocit};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    artists::model::{ArtistState, Draw},
+    model::SpriteType,
+    renderer::Renderer,
+};
+
+use super::{
+    components::{
+        Animation, Clickable, Collider, Health, Lane, Projectile, Shooter, SunProducer,
+        SwitchState, Transform,
+    },
+    model::{CollisionMargin, Offset, Pos},
+};
+
+// 快照：组件与动画帧，名称与绘制资源由 SpriteOrigin 重新构建
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpriteState {
+    pub transform: Transform,
+    pub lane: Option<Lane>,
+    pub animation: Option<Animation>,
+    pub health: Option<Health>,
+    pub collider: Option<Collider>,
+    pub clickable: Option<Clickable>,
+    pub projectile: Option<Projectile>,
+    pub shooter: Option<Shooter>,
+    pub sun_producer: Option<SunProducer>,
+    pub switch: Option<SwitchState>,
+    pub artist: ArtistState,
+}
 
+// 精灵只保存组件数据，行为与 systems 按需读取
 pub struct Sprite {
     pub name: SpriteType,
 
-    pub width: f64,
-    pub height: f64,
+    pub transform: Transform,
+
+    pub artist: Box<dyn Draw>,
 
-    pub velocit: Velocit,
+    pub lane: Option<Lane>,
 
-    pub draw_info: DrawInfo,
+    pub animation: Option<Animation>,
 
-    pub collision_margin: CollisionMargin,
+    pub health: Option<Health>,
 
-    pub artist: Box<dyn Draw>,
+    pub collider: Option<Collider>,
+
+    pub clickable: Option<Clickable>,
+
+    pub projectile: Option<Projectile>,
 
-    pub row: usize,
+    pub shooter: Option<Shooter>,
 
-    pub col: usize,
+    pub sun_producer: Option<SunProducer>,
 
-    pub clicked: bool,
+    pub switch: Option<SwitchState>,
 }
 
 impl Sprite {
-    pub fn new(
-        name: SpriteType,
-        artist: Box<dyn Draw>,
-        draw_info: DrawInfo,
-        collision_margin: CollisionMargin,
-    ) -> Sprite {
-        let cell = artist.as_ref().get_current_cell();
-        let (width, height) = match cell {
-            Some(cell) => (cell.width, cell.height),
-            None => (0.0, 0.0),
-        };
-
+    pub fn new(name: SpriteType, artist: Box<dyn Draw>, transform: Transform) -> Sprite {
         Sprite {
             name,
-            width,
-            height,
-
-            velocit: Default::default(),
-
-            collision_margin,
-
-            draw_info,
-
+            transform,
             artist,
 
-            row: 0,
-            col: 0,
-
-            clicked: false,
+            lane: None,
+            animation: None,
+            health: None,
+            collider: None,
+            clickable: None,
+            projectile: None,
+            shooter: None,
+            sun_producer: None,
+            switch: None,
         }
     }
-}
-
-impl Update for Sprite {
-    fn draw(&self, context: &CanvasRenderingContext2d) {
-        let DrawInfo { pos, .. } = &self.draw_info;
-
-        self.artist.draw(context, pos, &self.collision_margin);
-    }
-
-    fn get_artist(&mut self) -> &mut dyn Draw {
-        return self.artist.as_mut();
-    }
-
-    fn get_draw_info(&self) -> Option<&DrawInfo> {
-        return Some(&self.draw_info);
-    }
 
-    fn update_draw_info(&mut self, pos: Option<Pos>, offset: Option<Offset>) {
-        let DrawInfo { visible, order, .. } = self.draw_info;
-        let pos = match pos {
-            Some(pos) => pos,
-            None => self.draw_info.pos,
-        };
-        let offset = match offset {
-            Some(offset) => offset,
-            None => self.draw_info.offset,
+    pub fn draw(&self, renderer: &mut dyn Renderer) {
+        let margin = match self.collider {
+            Some(collider) => collider.margin,
+            None => CollisionMargin::no_collision(),
         };
 
-        self.draw_info = DrawInfo::new(pos, offset, visible, order);
+        self.artist.draw(renderer, &self.transform.pos, &margin);
     }
 
-    fn name(&self) -> SpriteType {
-        return self.name;
+    pub fn save(&self) -> SpriteState {
+        SpriteState {
+            transform: self.transform.clone(),
+            lane: self.lane,
+            animation: self.animation,
+            health: self.health,
+            collider: self.collider,
+            clickable: self.clickable,
+            projectile: self.projectile,
+            shooter: self.shooter.clone(),
+            sun_producer: self.sun_producer.clone(),
+            switch: self.switch,
+            artist: self.artist.save(),
+        }
     }
 
-    fn is_visible(&self) -> bool {
-        return self.draw_info.visible;
+    pub fn load(&mut self, state: &SpriteState) {
+        self.transform = state.transform.clone();
+        self.lane = state.lane;
+        self.animation = state.animation;
+        self.health = state.health;
+        self.collider = state.collider;
+        self.clickable = state.clickable;
+        self.projectile = state.projectile;
+        self.shooter = state.shooter.clone();
+        self.sun_producer = state.sun_producer.clone();
+        self.switch = state.switch;
+        self.artist.load(&state.artist);
     }
 
-    fn toggle(&mut self) {
-        self.draw_info.visible = !self.draw_info.visible;
+    pub fn update_transform(&mut self, pos: Option<Pos>, offset: Option<Offset>) {
+        if let Some(pos) = pos {
+            self.transform.pos = pos;
+        }
+
+        if let Some(offset) = offset {
+            self.transform.offset = offset;
+        }
     }
 
-    fn get_order(&self) -> usize {
-        self.draw_info.order
+    pub fn is_visible(&self) -> bool {
+        self.transform.visible
     }
 
-    fn update_loc(&mut self, row: usize, col: usize) {
-        self.row = row;
-        self.col = col;
+    pub fn toggle(&mut self) {
+        self.transform.visible = !self.transform.visible;
     }
 
-    fn get_loc(&self) -> (usize, usize) {
-        (self.row, self.col)
+    // 未放置在草坪上的精灵视为位于 (0, 0)
+    pub fn get_loc(&self) -> (usize, usize) {
+        match self.lane {
+            Some(Lane { row, col }) => (row, col),
+            None => (0, 0),
+        }
     }
 
-    fn get_read_artist(&self) -> &dyn Draw {
-        self.artist.as_ref()
+    pub fn is_clicked(&self) -> bool {
+        match self.clickable {
+            Some(clickable) => clickable.clicked,
+            None => false,
+        }
     }
 
-    fn is_clicked(&self) -> bool {
-        self.clicked
+    // 没有 Health 组件的精灵视为存活
+    pub fn is_alive(&self) -> bool {
+        match self.health {
+            Some(health) => health.life > 0.0,
+            None => true,
+        }
     }
 
-    fn set_clicked(&mut self, clicked: bool) {
-        self.clicked = clicked;
+    pub fn is_collision(&self) -> bool {
+        match self.collider {
+            Some(collider) => collider.collided,
+            None => false,
+        }
     }
 }
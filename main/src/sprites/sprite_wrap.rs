@@ -1,75 +1,100 @@
-use web_sys::CanvasRenderingContext2d;
-
 use crate::{
     behavior::model::{Behavior, BehaviorType},
-    callback::ErasedFnPointer,
-    model::{Interface, SpriteType},
+    entity::Entity,
+    event::{EventQueue, GameEvent},
+    model::{Callback, Interface, SpriteType},
+    renderer::Renderer,
+    snapshot::SpriteSnapshot,
+    systems,
 };
 
-use super::model::{DrawInfo, Offset, Pos, Update};
+use super::{
+    components::Transform,
+    model::{Offset, Pos, SpriteOrigin},
+    sprite::Sprite,
+};
 
 pub struct SpriteWrap {
-    pub sprite: Box<dyn Update>,
+    pub entity: Entity,
+    pub sprite: Sprite,
     pub behaviors: Vec<Box<dyn Behavior>>,
+    pub callbacks: Vec<(BehaviorType, Callback)>,
+    pub last_transform: Option<Transform>,
+    pub origin: Option<SpriteOrigin>,
 }
 
 impl SpriteWrap {
-    pub fn new(sprite: Box<dyn Update>, behaviors: Vec<Box<dyn Behavior>>) -> SpriteWrap {
-        SpriteWrap { behaviors, sprite }
-    }
-
-    pub fn find_sprite_behaviors(
-        sprites: &mut Vec<SpriteWrap>,
-        sprite_type: SpriteType,
-        behavior_type: BehaviorType,
-    ) -> Vec<&mut Box<dyn Behavior>> {
-        let mut behaviors: Vec<&mut Box<dyn Behavior>> = vec![];
-
-        for sprite in sprites {
-            if sprite.name() == sprite_type {
-                for behavior in &mut sprite.behaviors {
-                    if behavior.name() == behavior_type {
-                        behaviors.push(behavior);
-                    }
-                }
-            }
+    pub fn new(sprite: Sprite, behaviors: Vec<Box<dyn Behavior>>) -> SpriteWrap {
+        SpriteWrap {
+            entity: Default::default(),
+            behaviors,
+            sprite,
+            callbacks: vec![],
+            last_transform: None,
+            origin: None,
         }
+    }
 
-        return behaviors;
+    pub fn save(&self) -> SpriteSnapshot {
+        SpriteSnapshot {
+            entity: self.entity,
+            origin: self.origin.clone(),
+            sprite: self.sprite.save(),
+            behaviors: self
+                .behaviors
+                .iter()
+                .map(|behavior| behavior.save())
+                .collect(),
+            callbacks: self.callbacks.clone(),
+        }
     }
 
-    pub fn find_sprite_behavior(
-        sprites: &mut Vec<SpriteWrap>,
-        sprite_type: SpriteType,
-        behavior_type: BehaviorType,
-    ) -> Option<&mut Box<dyn Behavior>> {
-        let sprite = sprites
-            .iter_mut()
-            .find(|sprite| sprite.name() == sprite_type);
+    // 行为与数据一一对应，按顺序恢复
+    pub fn load(&mut self, snapshot: &SpriteSnapshot) {
+        self.entity = snapshot.entity;
+        self.sprite.load(&snapshot.sprite);
+        self.callbacks = snapshot.callbacks.clone();
 
-        return match sprite {
-            Some(sprite) => sprite
-                .behaviors
-                .iter_mut()
-                .find(|behavior| behavior.name() == behavior_type),
-            None => None,
-        };
+        for (behavior, state) in self.behaviors.iter_mut().zip(&snapshot.behaviors) {
+            behavior.load(state);
+        }
     }
 
-    pub fn has_behavior(&self, behavior_type: BehaviorType) -> bool {
-        return self
+    // 对象池复用：恢复到首次构建时的状态，再按新的数据放置并更新行为配置
+    pub fn reset(&mut self, template: &SpriteSnapshot, origin: SpriteOrigin) {
+        self.load(template);
+        self.last_transform = None;
+
+        let template_pos = self.sprite.transform.pos;
+        let pos = origin.data.pos.first().copied().unwrap_or(template_pos);
+        let offset = Pos::new(pos.left - template_pos.left, pos.top - template_pos.top);
+
+        // Cycle 构建为 Animation 组件，没有对应的行为
+        let behavior_data = origin
+            .data
             .behaviors
+            .iter()
+            .filter(|data| data.name != BehaviorType::Cycle);
+
+        for (behavior, data) in self.behaviors.iter_mut().zip(behavior_data) {
+            behavior.reuse(data, &offset);
+        }
+
+        self.sprite.transform.pos = pos;
+        self.origin = Some(origin);
+    }
+
+    pub fn has_behavior(&self, behavior_type: BehaviorType) -> bool {
+        self.behaviors
             .iter()
             .find(|behavior| behavior.name() == behavior_type)
-            .is_some();
+            .is_some()
     }
 
+    // 碰撞后停止行走，碰撞解除后继续
     pub fn before_update(&mut self, now: f64) {
-        match self.sprite.name() {
-            SpriteType::Zombie(_) => {
-                self.toggle_behaviors(&vec![BehaviorType::Walk], !self.is_collision(), now);
-            }
-            _ => (),
+        if self.sprite.collider.is_some() && self.has_behavior(BehaviorType::Collision) {
+            self.toggle_behaviors(&[BehaviorType::Walk], !self.is_collision(), now);
         }
     }
 
@@ -78,86 +103,133 @@ impl SpriteWrap {
         now: f64,
         last_animation_frame_time: f64,
         pos: &Pos,
-        context: &CanvasRenderingContext2d,
+        events: &mut EventQueue,
     ) {
         self.before_update(now);
 
+        systems::animate(&mut self.sprite, now);
+
         for behavior in &mut self.behaviors {
             if behavior.is_working() {
-                behavior.execute(
-                    self.sprite.as_mut(),
-                    now,
-                    last_animation_frame_time,
-                    pos,
-                    context,
-                );
+                behavior.execute(&mut self.sprite, now, last_animation_frame_time, pos);
+            }
+
+            if behavior.take_fired() {
+                if let Some(event) = GameEvent::from_behavior(behavior.name(), self.entity) {
+                    events.push(event);
+                }
             }
         }
     }
 
-    pub fn toggle_behaviors(&mut self, behavior_types: &Vec<BehaviorType>, run: bool, now: f64) {
+    pub fn toggle_behaviors(&mut self, behavior_types: &[BehaviorType], run: bool, now: f64) {
         for behavior in &mut self.behaviors {
-            match behavior_types.contains(&behavior.name()) {
-                true => match run {
+            if behavior_types.contains(&behavior.name()) {
+                match run {
                     true => behavior.start(now),
                     false => behavior.stop(now),
-                },
-                _ => (),
+                }
             }
         }
     }
 
-    pub fn draw(&self, context: &CanvasRenderingContext2d) {
-        if let Some(draw_info) = self.get_draw_info() {
-            let DrawInfo { offset, .. } = draw_info;
-
-            context.translate(-offset.x, -offset.y).unwrap();
+    pub fn toggle_animation(&mut self, playing: bool) {
+        if let Some(animation) = &mut self.sprite.animation {
+            animation.playing = playing;
+        }
+    }
 
-            self.sprite.draw(context);
+    pub fn pause_behaviors(&mut self, now: f64) {
+        for behavior in &mut self.behaviors {
+            behavior.pause(now);
+        }
+    }
 
-            context.translate(offset.x, offset.y).unwrap();
-        } else {
-            self.sprite.draw(context);
+    pub fn unpause_behaviors(&mut self, now: f64) {
+        for behavior in &mut self.behaviors {
+            behavior.unpause(now);
         }
     }
 
-    pub fn update_draw_info(&mut self, pos: Option<Pos>, offset: Option<Offset>) {
-        self.sprite.update_draw_info(pos, offset);
+    // 逻辑帧开始前记录位置，只有按速率移动的精灵需要插值
+    pub fn save_transform(&mut self) {
+        let moving = self.behaviors.iter_mut().any(|behavior| {
+            matches!(behavior.name(), BehaviorType::Walk | BehaviorType::Scroll)
+                && behavior.is_working()
+        });
+
+        self.last_transform = match moving {
+            true => Some(self.sprite.transform.clone()),
+            false => None,
+        };
     }
 
-    pub fn get_draw_info(&self) -> Option<&DrawInfo> {
-        return self.sprite.get_draw_info();
+    // alpha 为距当前逻辑帧的进度，返回在上一帧与当前帧之间插值后的绘制平移量
+    pub fn draw_offset(&self, alpha: f64) -> (f64, f64) {
+        let Transform { pos, offset, .. } = &self.sprite.transform;
+        let (x, y) = match &self.last_transform {
+            Some(last) => (
+                (last.pos.left - last.offset.x - pos.left + offset.x) * (1.0 - alpha),
+                (last.pos.top - last.offset.y - pos.top + offset.y) * (1.0 - alpha),
+            ),
+            None => (0.0, 0.0),
+        };
+
+        (x - offset.x, y - offset.y)
+    }
+
+    pub fn draw(&self, renderer: &mut dyn Renderer, alpha: f64) {
+        let (x, y) = self.draw_offset(alpha);
+
+        renderer.translate(x, y);
+
+        self.sprite.draw(renderer);
+
+        renderer.translate(-x, -y);
+    }
+
+    pub fn update_transform(&mut self, pos: Option<Pos>, offset: Option<Offset>) {
+        self.sprite.update_transform(pos, offset);
+    }
+
+    pub fn get_transform(&self) -> &Transform {
+        &self.sprite.transform
     }
 
     pub fn is_visible(&self) -> bool {
-        return self.sprite.is_visible();
+        self.sprite.is_visible()
     }
 
     pub fn name(&self) -> SpriteType {
-        return self.sprite.name();
+        self.sprite.name
     }
 
-    pub fn register_callback(&mut self, behavior_type: BehaviorType, pointer: ErasedFnPointer) {
-        let behavior = self
-            .behaviors
-            .iter_mut()
-            .find(|behavior| behavior.name() == behavior_type);
-
-        if let Some(behavior) = behavior {
-            behavior.set_cb(pointer);
+    // 行为触发事件后由 Game 执行的回调，每种行为只保留最后注册的一个
+    pub fn register_callback(&mut self, behavior_type: BehaviorType, callback: Callback) {
+        if self.has_behavior(behavior_type) {
+            self.callbacks
+                .retain(|(registered, _)| *registered != behavior_type);
+            self.callbacks.push((behavior_type, callback));
         }
     }
 
+    pub fn get_callback(&self, behavior_type: BehaviorType) -> Option<Callback> {
+        self.callbacks
+            .iter()
+            .find(|(name, _)| *name == behavior_type)
+            .map(|(_, callback)| *callback)
+    }
+
     pub fn update_scale(&mut self, scale: f64) {
-        self.sprite.get_artist().update_scale(scale);
+        self.sprite.artist.update_scale(scale);
     }
 
     pub fn get_order(&self) -> usize {
-        return self.sprite.get_order();
+        self.sprite.transform.order
     }
 
     pub fn get_loc(&self) -> (usize, usize) {
-        return self.sprite.get_loc();
+        self.sprite.get_loc()
     }
 
     pub fn is_collision(&self) -> bool {
@@ -169,11 +241,14 @@ impl SpriteWrap {
     }
 
     pub fn is_plant(&self) -> bool {
-        match self.sprite.name() {
-            SpriteType::Interface(Interface::LawnCleaner) => true,
-            SpriteType::Plant(_) => true,
-            _ => false,
-        }
+        matches!(
+            self.sprite.name,
+            SpriteType::Interface(Interface::LawnCleaner) | SpriteType::Plant
+        )
+    }
+
+    pub fn is_zombie(&self) -> bool {
+        matches!(self.sprite.name, SpriteType::Zombie)
     }
 
     pub fn can_check_collision(&self) -> bool {
@@ -0,0 +1,171 @@
+use crate::{
+    artists::model::Draw,
+    behavior::collision::CollisionBehavior,
+    event::GameEvent,
+    sprites::{
+        components::{Animation, SunProducer, SwitchState},
+        model::Pos,
+        sprite::Sprite,
+    },
+};
+
+// 僵尸的动画分组：0 行走，1 攻击
+const WALKING: usize = 0;
+const ATTACKING: usize = 1;
+
+// 事件先交给精灵的组件处理，再执行注册的回调
+pub fn handle_event(sprite: &mut Sprite, event: &GameEvent) {
+    match event {
+        GameEvent::IntervalElapsed(_) => {
+            if let Some(shooter) = &mut sprite.shooter {
+                shooter.ready = true;
+            }
+
+            if let Some(switch) = &mut sprite.switch {
+                switch.active = true;
+            }
+        }
+        GameEvent::SwitchFinished(_) => {
+            if let Some(switch) = &mut sprite.switch {
+                switch.active = false;
+            }
+
+            if let Some(sun_producer) = &mut sprite.sun_producer {
+                sun_producer.ready = true;
+            }
+        }
+        _ => (),
+    }
+}
+
+// 返回发生碰撞的候选精灵下标
+pub fn find_collision(sprite: &Sprite, candidates: &Vec<&Sprite>) -> Option<usize> {
+    candidates.iter().position(|candidate| {
+        CollisionBehavior::is_candidate_for_collision(sprite, candidate)
+            && CollisionBehavior::did_collide(sprite, candidate)
+    })
+}
+
+// 开始碰撞时切换到攻击，碰撞解除时恢复行走
+pub fn update_collision(sprite: &mut Sprite, collided: bool) {
+    let last_collided = match &mut sprite.collider {
+        Some(collider) => std::mem::replace(&mut collider.collided, collided),
+        None => return,
+    };
+
+    match (last_collided, collided) {
+        (false, true) => sprite.switch = Some(SwitchState::new(true, ATTACKING)),
+        (true, false) => sprite.switch = Some(SwitchState::new(true, WALKING)),
+        _ => (),
+    }
+}
+
+// 子弹的碰撞区域与僵尸的碰撞区域左右重叠即为击中，行由空间索引保证
+pub fn did_hit(projectile: &Sprite, target: &Sprite) -> bool {
+    let bounds = |sprite: &Sprite| {
+        let pos = sprite.transform.pos;
+        let width = match sprite.artist.get_current_cell() {
+            Some(cell) => cell.width,
+            None => 0.0,
+        };
+
+        match sprite.collider {
+            Some(collider) => (
+                pos.left + collider.margin.left,
+                pos.left + width - collider.margin.right,
+            ),
+            None => (pos.left, pos.left + width),
+        }
+    };
+    let (left, right) = bounds(projectile);
+    let (o_left, o_right) = bounds(target);
+
+    projectile.is_visible() && target.is_visible() && left <= o_right && right >= o_left
+}
+
+// 生命值降到 0 时隐藏，下一次回收时移除
+pub fn damage(sprite: &mut Sprite, amount: f64) {
+    if let Some(health) = &mut sprite.health {
+        health.life -= amount;
+
+        if health.life <= 0.0 {
+            sprite.transform.visible = false;
+        }
+    }
+}
+
+// 每个逻辑帧 elapsed 毫秒内造成的伤害
+pub fn attack_damage(sprite: &Sprite, elapsed: f64) -> f64 {
+    match sprite.health {
+        Some(health) => health.attack * elapsed / 1000.0,
+        None => 0.0,
+    }
+}
+
+// 子弹击中后消失，伤害只计算一次
+pub fn hit(projectile: &mut Sprite) -> f64 {
+    let attack = match projectile.projectile {
+        Some(projectile) => projectile.attack,
+        None => 0.0,
+    };
+
+    projectile.transform.visible = false;
+
+    attack
+}
+
+pub fn animate(sprite: &mut Sprite, now: f64) {
+    if let Some(animation) = &mut sprite.animation {
+        if animation.playing {
+            advance(animation, sprite.artist.as_mut(), now);
+        }
+    }
+}
+
+// 距上次切换超过 duration 时切到下一帧；有 interval 时最后一帧停留 interval
+pub fn advance(animation: &mut Animation, artist: &mut dyn Draw, now: f64) {
+    let elapsed = now - animation.last_advance;
+    let should_advance = match animation.interval {
+        Some(interval) if artist.in_last_cell() => elapsed > interval,
+        _ => elapsed > animation.duration,
+    };
+
+    if should_advance {
+        artist.advance();
+        animation.last_advance = now;
+    }
+}
+
+// 子弹从植物右侧发出；前方没有僵尸时保持就绪，等僵尸进入再发射
+pub fn take_shot(sprite: &mut Sprite, has_target: bool) -> Option<(Pos, String)> {
+    let pos = sprite.transform.pos;
+    let width = match sprite.artist.get_current_cell() {
+        Some(cell) => cell.width,
+        None => 0.0,
+    };
+
+    match &mut sprite.shooter {
+        Some(shooter) if shooter.ready && has_target => {
+            shooter.ready = false;
+
+            Some((
+                Pos::new(pos.left + width / 1.5, pos.top),
+                shooter.projectile.clone(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+pub fn take_sun(sprite: &mut Sprite) -> Option<Pos> {
+    let pos = sprite.transform.pos;
+
+    match &mut sprite.sun_producer {
+        Some(SunProducer { offset, ready }) if *ready => {
+            *ready = false;
+
+            Some(Pos::new(pos.left + offset.x, pos.top + offset.y))
+        }
+        _ => None,
+    }
+}
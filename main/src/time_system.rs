@@ -1,55 +1,305 @@
-use crate::timer::{animation_timer::AnimationTimer, base_timer::Timer, model::Elapsed};
+use serde::{Deserialize, Serialize};
+
+use crate::timer::{animation_timer::AnimationTimer, clock::Clock, model::Elapsed};
 
 pub type Transducer = fn(f64) -> f64;
 
+// 逻辑帧固定 60 Hz，与渲染帧率无关
+pub const TICK_DURATION: f64 = 1000.0 / 60.0;
+
+// 单次渲染最多追赶的逻辑帧数，避免长时间卡顿后一次性补算过多
+pub const MAX_TICKS_PER_FRAME: u32 = 8;
+
 pub fn default_transducer(elapsed_time: f64) -> f64 {
     elapsed_time
 }
 
+/* Speed */
+// 游戏速度档位，通过变速函数缩放游戏时间，所有行为一致加速或减速
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Speed {
+    Half,
+    #[default]
+    Normal,
+    Double,
+    Triple,
+}
+
+impl Speed {
+    pub fn rate(&self) -> f64 {
+        match self {
+            Speed::Half => 0.5,
+            Speed::Normal => 1.0,
+            Speed::Double => 2.0,
+            Speed::Triple => 3.0,
+        }
+    }
+
+    pub fn from_rate(rate: f64) -> Option<Speed> {
+        [Speed::Half, Speed::Normal, Speed::Double, Speed::Triple]
+            .iter()
+            .find(|speed| speed.rate() == rate)
+            .copied()
+    }
+
+    // 按键循环切换：1x -> 2x -> 3x -> 0.5x -> 1x
+    pub fn next(&self) -> Speed {
+        match self {
+            Speed::Half => Speed::Normal,
+            Speed::Normal => Speed::Double,
+            Speed::Double => Speed::Triple,
+            Speed::Triple => Speed::Half,
+        }
+    }
+
+    pub fn transducer(&self) -> Transducer {
+        match self {
+            Speed::Half => |elapsed_time| elapsed_time * 0.5,
+            Speed::Normal => default_transducer,
+            Speed::Double => |elapsed_time| elapsed_time * 2.0,
+            Speed::Triple => |elapsed_time| elapsed_time * 3.0,
+        }
+    }
+}
+
 pub struct TimeSystem {
     pub game_time: f64,
     pub last_time_transducer_was_set: f64,
+    pub tick_time: f64,
+    pub ticks: u64,
     pub timer: Box<dyn Elapsed>,
+    accumulator: f64,
+    clock: Box<dyn Clock>,
     transducer: Transducer,
+    // 临时变速结束后恢复的变速函数，以及剩余的真实时长
+    revert: Option<(Transducer, f64)>,
 }
 
 impl TimeSystem {
-    pub fn new() -> TimeSystem {
+    pub fn new(clock: Box<dyn Clock>) -> TimeSystem {
         TimeSystem {
             game_time: 0.0,
             last_time_transducer_was_set: 0.0,
+            tick_time: 0.0,
+            ticks: 0,
             timer: Box::new(AnimationTimer::new(1000.0)),
+            accumulator: 0.0,
+            clock,
             transducer: default_transducer,
+            revert: None,
         }
     }
 
+    // 真实时间，不受变速与暂停影响
+    pub fn real_time(&self) -> f64 {
+        self.clock.now()
+    }
+
     pub fn calculate_game_time(&mut self) -> f64 {
-        self.game_time = self.last_time_transducer_was_set
-            + (self.transducer)(self.timer.get_elapsed_time(Timer::get_current_time()));
+        let elapsed_time = self.timer.get_elapsed_time(self.clock.now());
+        let game_elapsed_time = match &mut self.revert {
+            // 临时变速到期：到期前按临时变速，之后按恢复的变速
+            Some((transducer, remaining)) if elapsed_time >= *remaining => {
+                let game_elapsed_time =
+                    (self.transducer)(*remaining) + transducer(elapsed_time - *remaining);
+
+                self.transducer = *transducer;
+                self.revert = None;
+
+                game_elapsed_time
+            }
+            Some((_, remaining)) => {
+                *remaining -= elapsed_time;
+
+                (self.transducer)(elapsed_time)
+            }
+            None => (self.transducer)(elapsed_time),
+        };
+
+        self.game_time = self.last_time_transducer_was_set + game_elapsed_time;
         self.reset();
 
-        return self.game_time;
+        self.game_time
+    }
+
+    // 把上次结算以来的游戏时间计入待执行的逻辑帧
+    fn accumulate(&mut self) {
+        let last_game_time = self.game_time;
+
+        self.accumulator += self.calculate_game_time() - last_game_time;
+    }
+
+    // 累计本次渲染经过的游戏时间，返回需要执行的逻辑帧数
+    pub fn advance(&mut self) -> u32 {
+        if self.is_paused() {
+            return 0;
+        }
+
+        self.accumulate();
+
+        // 用除法计数，反复相减的浮点误差会在追赶上限处少算一帧
+        let ticks = (self.accumulator / TICK_DURATION).floor() as u32;
+
+        if ticks >= MAX_TICKS_PER_FRAME {
+            self.accumulator = 0.0;
+
+            return MAX_TICKS_PER_FRAME;
+        }
+
+        self.accumulator = (self.accumulator - ticks as f64 * TICK_DURATION).max(0.0);
+
+        ticks
+    }
+
+    // 推进一个逻辑帧，返回该帧的逻辑时间
+    pub fn tick(&mut self) -> f64 {
+        self.tick_time += TICK_DURATION;
+        self.ticks += 1;
+
+        self.tick_time
+    }
+
+    // 剩余不足一帧的时间占比，用于在最近两个逻辑状态之间插值绘制
+    pub fn alpha(&self) -> f64 {
+        self.accumulator / TICK_DURATION
     }
 
     pub fn start(&mut self) {
-        self.timer.start(Timer::get_current_time());
+        self.timer.start(self.clock.now());
+    }
+
+    // 暂停期间游戏时间停止，恢复后不补算暂停的时长
+    pub fn pause(&mut self) {
+        if self.is_paused() {
+            return;
+        }
+
+        // 先结算暂停前的游戏时间，暂停期间不再调用 calculate_game_time
+        self.accumulate();
+
+        let now = self.clock.now();
+
+        self.timer.pause(now);
+    }
+
+    pub fn resume(&mut self) {
+        let now = self.clock.now();
+
+        self.timer.unpause(now);
+    }
+
+    pub fn is_paused(&mut self) -> bool {
+        self.timer.get_timer().is_paused()
     }
 
     pub fn reset(&mut self) {
-        self.timer.reboot(Timer::get_current_time());
+        self.timer.reboot(self.clock.now());
         self.last_time_transducer_was_set = self.game_time;
     }
 
-    pub fn _set_transducer(&mut self, transducer: Transducer, duration: Option<u16>) {
-        let _last_transducer = self.transducer;
+    // 给定 duration（毫秒，真实时间）时为临时变速，到期后恢复为设置前的变速
+    pub fn set_transducer(&mut self, transducer: Transducer, duration: Option<f64>) {
+        // 暂停时已经结算过，结算会重启计时器从而解除暂停
+        if !self.is_paused() {
+            self.accumulate();
+        }
+
+        // 临时变速期间再次设置，仍恢复到最初的变速
+        let last_transducer = match self.revert.take() {
+            Some((transducer, _)) => transducer,
+            None => self.transducer,
+        };
 
-        self.calculate_game_time();
-        self.reset();
         self.transducer = transducer;
+        self.revert = duration.map(|duration| (last_transducer, duration));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::clock::ManualClock;
+
+    fn time_system() -> (TimeSystem, ManualClock) {
+        let clock = ManualClock::new();
+        let mut time_system = TimeSystem::new(Box::new(clock.clone()));
+
+        time_system.start();
+
+        (time_system, clock)
+    }
 
-        if let Some(_duration) = duration {
-            todo!();
-            // self.set_transducer(transducer, None);
+    // 按 frame 毫秒一帧推进 duration 毫秒真实时间，返回执行的逻辑帧数
+    fn run(time_system: &mut TimeSystem, clock: &ManualClock, duration: f64, frame: f64) -> u32 {
+        let mut ticks = 0;
+        let mut elapsed = 0.0;
+
+        while elapsed < duration {
+            clock.advance(frame);
+            elapsed += frame;
+            ticks += time_system.advance();
         }
+
+        // 多走四分之一逻辑帧，避免浮点误差恰好落在帧边界上
+        clock.advance(TICK_DURATION / 4.0);
+
+        ticks + time_system.advance()
+    }
+
+    #[test]
+    fn normal_speed_runs_60_ticks_per_second() {
+        let (mut time_system, clock) = time_system();
+
+        assert_eq!(run(&mut time_system, &clock, 1000.0, 10.0), 60);
+    }
+
+    #[test]
+    fn double_speed_runs_120_ticks_per_second() {
+        let (mut time_system, clock) = time_system();
+
+        time_system.set_transducer(Speed::Double.transducer(), None);
+
+        assert_eq!(run(&mut time_system, &clock, 1000.0, 10.0), 120);
+    }
+
+    // 超出追赶上限的时间直接丢弃
+    #[test]
+    fn long_frame_is_clamped() {
+        let (mut time_system, clock) = time_system();
+
+        clock.advance(10_000.0);
+
+        assert_eq!(time_system.advance(), MAX_TICKS_PER_FRAME);
+        assert_eq!(time_system.alpha(), 0.0);
+
+        clock.advance(TICK_DURATION / 2.0);
+
+        assert_eq!(time_system.advance(), 0);
+    }
+
+    #[test]
+    fn temporary_transducer_reverts_after_its_duration() {
+        let (mut time_system, clock) = time_system();
+
+        time_system.set_transducer(Speed::Double.transducer(), Some(500.0));
+
+        // 前 500 毫秒按 2 倍，之后恢复为 1 倍
+        clock.advance(1000.0);
+
+        assert_eq!(time_system.calculate_game_time(), 1500.0);
+
+        clock.advance(1000.0);
+
+        assert_eq!(time_system.calculate_game_time(), 2500.0);
+    }
+
+    #[test]
+    fn temporary_transducer_spanning_frames_reverts_once() {
+        let (mut time_system, clock) = time_system();
+
+        time_system.set_transducer(Speed::Half.transducer(), Some(400.0));
+
+        assert_eq!(run(&mut time_system, &clock, 400.0, 10.0), 12);
+        assert_eq!(run(&mut time_system, &clock, 1000.0, 10.0), 60);
     }
 }
@@ -1,9 +1,18 @@
+use serde::{Deserialize, Serialize};
+
 use super::{
     base_timer::Timer,
     model::{Easing, Time},
     Elapsed,
 };
 
+// 快照只保存计时状态，duration 与缓动由精灵数据重新构建
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AnimationTimerState {
+    pub timer: Timer,
+    pub last_finished_time: f64,
+}
+
 pub struct AnimationTimer {
     pub duration: f64,
     pub timer: Timer,
@@ -22,21 +31,33 @@ impl AnimationTimer {
     }
 
     pub fn is_finished(&self, now: f64) -> bool {
-        return now - self.last_finished_time > self.duration;
+        now - self.last_finished_time > self.duration
     }
 
     pub fn is_expired(&self, now: f64) -> bool {
-        return self.get_elapsed_time(now) > self.duration;
+        self.get_elapsed_time(now) > self.duration
     }
 
     pub fn finisehd(&mut self, now: f64) {
         self.last_finished_time = now;
     }
+
+    pub fn save(&self) -> AnimationTimerState {
+        AnimationTimerState {
+            timer: self.timer.clone(),
+            last_finished_time: self.last_finished_time,
+        }
+    }
+
+    pub fn load(&mut self, state: &AnimationTimerState) {
+        self.timer = state.timer.clone();
+        self.last_finished_time = state.last_finished_time;
+    }
 }
 
 impl Time for AnimationTimer {
     fn get_timer(&mut self) -> &mut Timer {
-        return &mut self.timer;
+        &mut self.timer
     }
 }
 
@@ -49,9 +70,9 @@ impl Elapsed for AnimationTimer {
             return elapsed_time;
         }
 
-        return match &self.easing {
+        match &self.easing {
             Some(easing) => elapsed_time * (easing.calc(percent_complete) / percent_complete),
             None => elapsed_time,
-        };
+        }
     }
 }
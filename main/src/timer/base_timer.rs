@@ -1,8 +1,8 @@
-use crate::util::window;
+use serde::{Deserialize, Serialize};
 
 use super::model::{Elapsed, Time};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Timer {
     elapsed: f64,
     paused: bool,
@@ -13,20 +13,12 @@ pub struct Timer {
 }
 
 impl Timer {
-    pub fn get_current_time() -> f64 {
-        let performance = window()
-            .performance()
-            .expect("performance should be available");
-
-        performance.now()
-    }
-
     pub fn is_paused(&self) -> bool {
-        return self.paused;
+        self.paused
     }
 
     pub fn is_running(&self) -> bool {
-        return self.running;
+        self.running
     }
 
     pub fn pause(&mut self, now: f64) {
@@ -60,7 +52,7 @@ impl Timer {
 
 impl Time for Timer {
     fn get_timer(&mut self) -> &mut Timer {
-        return self;
+        self
     }
 
     fn start(&mut self, now: f64) {
@@ -88,9 +80,9 @@ impl Time for Timer {
 
 impl Elapsed for Timer {
     fn get_elapsed_time(&self, now: f64) -> f64 {
-        return match self.running {
+        match self.running {
             true => now - self.start_time - self.total_paused_time,
             false => self.elapsed,
-        };
+        }
     }
 }
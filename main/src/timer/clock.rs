@@ -0,0 +1,49 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::util::window;
+
+/* *************** trait *************** */
+
+pub trait Clock {
+    fn now(&self) -> f64;
+}
+
+/* PerformanceClock */
+pub struct PerformanceClock;
+
+impl Clock for PerformanceClock {
+    fn now(&self) -> f64 {
+        let performance = window()
+            .performance()
+            .expect("performance should be available");
+
+        performance.now()
+    }
+}
+
+/* ManualClock */
+// 由调用方推进时间，用于原生环境下逐帧驱动游戏
+#[derive(Debug, Default, Clone)]
+pub struct ManualClock {
+    time: Rc<Cell<f64>>,
+}
+
+impl ManualClock {
+    pub fn new() -> ManualClock {
+        Default::default()
+    }
+
+    pub fn advance(&self, delta: f64) {
+        self.time.set(self.time.get() + delta);
+    }
+
+    pub fn set(&self, now: f64) {
+        self.time.set(now);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> f64 {
+        self.time.get()
+    }
+}
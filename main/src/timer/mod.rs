@@ -2,5 +2,6 @@ use self::model::Elapsed;
 
 pub mod animation_timer;
 pub mod base_timer;
+pub mod clock;
 pub mod easing;
 pub mod model;
@@ -31,9 +31,21 @@ pub trait Time {
         timer.reboot(now);
     }
 
+    fn pause(&mut self, now: f64) {
+        let timer = self.get_timer();
+
+        timer.pause(now);
+    }
+
+    fn unpause(&mut self, now: f64) {
+        let timer = self.get_timer();
+
+        timer.unpause(now);
+    }
+
     fn is_working(&mut self) -> bool {
         let timer = self.get_timer();
 
-        return timer.is_running() && !timer.is_paused();
+        timer.is_running() && !timer.is_paused()
     }
 }
@@ -1,7 +1,11 @@
+#[cfg(target_arch = "wasm32")]
 use js_sys::Math;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::{prelude::Closure, JsCast};
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::rng::DEFAULT_SEED;
+
 pub fn window() -> web_sys::Window {
     web_sys::window().expect("no global `window` exists")
 }
@@ -17,33 +21,28 @@ pub fn init() {
     console_error_panic_hook::set_once();
 }
 
-pub fn get_random_int(min: f64, max: f64) -> f64 {
-    let min = Math::ceil(min);
-    let max = Math::floor(max);
-
-    return Math::floor(Math::random() * (max - min)) + min;
+// 关卡与调用方都没有指定种子时使用：浏览器端每局不同，原生环境固定以便复现
+#[cfg(target_arch = "wasm32")]
+pub fn random_seed() -> u64 {
+    (Math::random() * u64::MAX as f64) as u64
 }
 
-#[macro_export]
-macro_rules! log {
-    ($($t:tt)*) => {
-        web_sys::console::log_1(&format!($($t)*).into())
-    }
+#[cfg(not(target_arch = "wasm32"))]
+pub fn random_seed() -> u64 {
+    DEFAULT_SEED
 }
 
-#[macro_export]
-macro_rules! asset_json {
-    ($name: tt) => {
-        concat!("main/assets/jsons/", $name, ".json")
-    };
+pub fn console_log(message: &str) {
+    #[cfg(target_arch = "wasm32")]
+    web_sys::console::log_1(&message.into());
+
+    #[cfg(not(target_arch = "wasm32"))]
+    println!("{}", message);
 }
 
 #[macro_export]
-macro_rules! asset_image {
-    ($name: tt) => {
-        (concat!("main/assets/images/", $name), ".png")
-    };
-    ($name: tt, $ext: tt) => {
-        (concat!("main/assets/images/", $name), concat!(".", $ext))
-    };
+macro_rules! log {
+    ($($t:tt)*) => {
+        $crate::util::console_log(&format!($($t)*))
+    }
 }
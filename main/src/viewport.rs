@@ -0,0 +1,134 @@
+use crate::{
+    data::{CANVAS_HEIGHT, CANVAS_WIDTH},
+    sprites::model::Pos,
+};
+
+/* Viewport */
+// 逻辑坐标（CANVAS_WIDTH x CANVAS_HEIGHT）与画布元素坐标（CSS 像素）之间的换算，
+// 游戏区域等比缩放后居中，多余部分留黑边
+#[derive(Debug, Clone, Copy)]
+pub struct Viewport {
+    pub width: f64,
+    pub height: f64,
+    pub pixel_ratio: f64,
+    pub scale: f64,
+    pub offset: Pos,
+}
+
+impl Viewport {
+    pub fn new(width: f64, height: f64, pixel_ratio: f64) -> Viewport {
+        let logical_width = CANVAS_WIDTH as f64;
+        let logical_height = CANVAS_HEIGHT as f64;
+        let scale = (width / logical_width).min(height / logical_height);
+        let offset = Pos::new(
+            (width - logical_width * scale) / 2.0,
+            (height - logical_height * scale) / 2.0,
+        );
+
+        Viewport {
+            width,
+            height,
+            pixel_ratio,
+            scale,
+            offset,
+        }
+    }
+
+    // 画布元素上的 CSS 像素坐标转成逻辑坐标
+    pub fn to_logical(&self, x: f64, y: f64) -> Pos {
+        Pos::new(
+            (x - self.offset.left) / self.scale,
+            (y - self.offset.top) / self.scale,
+        )
+    }
+
+    // 逻辑坐标转成画布的物理像素坐标
+    pub fn to_physical(&self, pos: &Pos) -> Pos {
+        Pos::new(
+            (pos.left * self.scale + self.offset.left) * self.pixel_ratio,
+            (pos.top * self.scale + self.offset.top) * self.pixel_ratio,
+        )
+    }
+
+    // 画布实际的像素尺寸，高分屏下大于 CSS 尺寸
+    pub fn backing_size(&self) -> (u32, u32) {
+        (
+            (self.width * self.pixel_ratio).round() as u32,
+            (self.height * self.pixel_ratio).round() as u32,
+        )
+    }
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Viewport::new(CANVAS_WIDTH as f64, CANVAS_HEIGHT as f64, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 横向多余：左右留黑边
+    #[test]
+    fn wide_container_letterboxes_left_and_right() {
+        let viewport = Viewport::new(1800.0, 600.0, 1.0);
+
+        assert_eq!(viewport.scale, 1.0);
+        assert_eq!(viewport.offset, Pos::new(450.0, 0.0));
+        assert_eq!(viewport.to_logical(450.0, 0.0), Pos::new(0.0, 0.0));
+        assert_eq!(viewport.to_logical(1350.0, 600.0), Pos::new(900.0, 600.0));
+        // 黑边上的点在逻辑区域之外
+        assert!(viewport.to_logical(100.0, 300.0).left < 0.0);
+    }
+
+    // 纵向多余：上下留黑边
+    #[test]
+    fn tall_container_letterboxes_top_and_bottom() {
+        let viewport = Viewport::new(450.0, 900.0, 1.0);
+
+        assert_eq!(viewport.scale, 0.5);
+        assert_eq!(viewport.offset, Pos::new(0.0, 300.0));
+        assert_eq!(viewport.to_logical(225.0, 450.0), Pos::new(450.0, 300.0));
+        assert_eq!(
+            viewport.to_physical(&Pos::new(900.0, 600.0)),
+            Pos::new(450.0, 600.0)
+        );
+    }
+
+    // 设备像素比只影响物理像素，逻辑坐标仍按 CSS 像素换算
+    #[test]
+    fn pixel_ratio_scales_physical_pixels() {
+        let viewport = Viewport::new(900.0, 600.0, 2.0);
+
+        assert_eq!(viewport.backing_size(), (1800, 1200));
+        assert_eq!(viewport.to_logical(300.0, 200.0), Pos::new(300.0, 200.0));
+        assert_eq!(
+            viewport.to_physical(&Pos::new(300.0, 200.0)),
+            Pos::new(600.0, 400.0)
+        );
+    }
+
+    // 物理像素除以设备像素比即 CSS 像素，换算回来是原来的逻辑坐标
+    #[test]
+    fn round_trip() {
+        let viewport = Viewport::new(1280.0, 720.0, 1.5);
+
+        for pos in [
+            Pos::new(0.0, 0.0),
+            Pos::new(123.0, 456.0),
+            Pos::new(900.0, 600.0),
+        ]
+        .iter()
+        {
+            let physical = viewport.to_physical(pos);
+            let logical = viewport.to_logical(
+                physical.left / viewport.pixel_ratio,
+                physical.top / viewport.pixel_ratio,
+            );
+
+            assert!((logical.left - pos.left).abs() < 1e-9);
+            assert!((logical.top - pos.top).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::{fs, path::Path};
+
+use plants_vs_zombies::{
+    atlas::Atlas,
+    sprites::model::{CellTrim, SpriteCell},
+};
+
+fn fixture(name: &str) -> Atlas {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/atlas")
+        .join(name);
+
+    Atlas::parse(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn names(atlas: &Atlas) -> Vec<&str> {
+    atlas
+        .frames
+        .iter()
+        .map(|frame| frame.name.as_str())
+        .collect()
+}
+
+fn rect(cell: &SpriteCell) -> (f64, f64, f64, f64) {
+    (cell.left, cell.top, cell.width, cell.height)
+}
+
+// hash 格式保持文件中的顺序，分组后按帧序号排序
+#[test]
+fn reads_texture_packer_hash() {
+    let atlas = fixture("hash.json");
+
+    assert_eq!(
+        names(&atlas),
+        vec![
+            "ZombieWalk_2.png",
+            "ZombieWalk_10.png",
+            "ZombieWalk_1.png",
+            "Sun.png"
+        ]
+    );
+
+    let trimmed = atlas.frames[0].cell;
+
+    assert_eq!(
+        trimmed.trim,
+        Some(CellTrim {
+            x: 4.0,
+            y: 6.0,
+            width: 90.0,
+            height: 130.0,
+        })
+    );
+    assert_eq!(trimmed.pivot, Some((0.5, 1.0)));
+    assert_eq!(atlas.frames[1].cell.trim, None);
+
+    let cells = atlas.to_cells("zombie");
+    let walk: Vec<f64> = cells["zombie/ZombieWalk"]
+        .iter()
+        .map(|cell| cell.left)
+        .collect();
+
+    assert_eq!(walk, vec![20.0, 100.0, 180.0]);
+    assert_eq!(rect(&cells["zombie/Sun"][0]), (0.0, 120.0, 78.0, 78.0));
+}
+
+#[test]
+fn reads_texture_packer_array() {
+    let atlas = fixture("array.json");
+
+    assert_eq!(
+        names(&atlas),
+        vec![
+            "Peashooter/0002.png",
+            "Peashooter/0001.png",
+            "SunFlower1.png"
+        ]
+    );
+
+    let cells = atlas.to_cells("plant");
+    let peashooter = &cells["plant/Peashooter"];
+
+    assert_eq!(rect(&peashooter[0]), (0.0, 0.0, 71.0, 71.0));
+    assert_eq!(
+        peashooter[0].trim,
+        Some(CellTrim {
+            x: 2.0,
+            y: 3.0,
+            width: 75.0,
+            height: 76.0,
+        })
+    );
+    assert_eq!(rect(&peashooter[1]), (71.0, 0.0, 71.0, 71.0));
+    // 没有分隔符的数字是名称的一部分
+    assert_eq!(cells["plant/SunFlower1"].len(), 1);
+}
+
+// 属性值中的 > 不结束标签，实体与单引号都能识别
+#[test]
+fn reads_sparrow_xml() {
+    let atlas = fixture("sheet.xml");
+
+    assert_eq!(
+        names(&atlas),
+        vec![
+            "Button>Hover_2.png",
+            "Button>Hover_1.png",
+            "Card \"Sun\".png"
+        ]
+    );
+
+    let card = atlas.frames[2].cell;
+
+    assert_eq!(rect(&card), (0.0, 40.0, 50.0, 70.0));
+    assert_eq!(
+        card.trim,
+        Some(CellTrim {
+            x: 2.0,
+            y: 3.0,
+            width: 54.0,
+            height: 76.0,
+        })
+    );
+    assert_eq!(card.pivot, Some((0.5, 1.0)));
+
+    let cells = atlas.to_cells("interface");
+    let hover: Vec<f64> = cells["interface/Button>Hover"]
+        .iter()
+        .map(|cell| cell.left)
+        .collect();
+
+    assert_eq!(hover, vec![0.0, 120.0]);
+}
+
+#[test]
+fn rejects_rotated_and_unclosed_frames() {
+    let rotated = r#"{ "frames": [{ "filename": "a.png", "rotated": true,
+        "frame": { "x": 0, "y": 0, "w": 1, "h": 1 } }] }"#;
+
+    assert!(Atlas::parse(rotated).is_err());
+    assert!(Atlas::parse(r#"<SubTexture name="a>b" x="0""#).is_err());
+}
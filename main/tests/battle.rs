@@ -0,0 +1,113 @@
+mod common;
+
+use plants_vs_zombies::{scene::model::SceneKind, sprites::model::Pos};
+
+#[test]
+fn level_1_1_runs_headless() {
+    let mut headless = common::battle(7);
+
+    assert_eq!(headless.game.current_scene(), Some(SceneKind::Battle));
+    assert_eq!(headless.game.cur_level.name, "1-1");
+
+    // 准备动画结束后才种下植物、放出僵尸
+    headless.run_for(5_000.0, 16.0);
+
+    let plants = headless
+        .game
+        .sprites
+        .iter()
+        .filter(|sprite| sprite.is_plant() && sprite.sprite.lane.is_some())
+        .count();
+    let start = common::zombie_lefts(&headless);
+    let lives = common::zombie_lives(&headless);
+
+    assert!(plants > 0);
+    assert!(!start.is_empty());
+
+    headless.run_for(20_000.0, 16.0);
+
+    let game = &headless.game;
+    let now = common::zombie_lefts(&headless);
+
+    assert_eq!(game.current_scene(), Some(SceneKind::Battle));
+    assert!(game.time_system.ticks >= 1200);
+    // 射手已经开火击中僵尸，僵尸在向左走
+    assert!(common::zombie_lives(&headless)
+        .iter()
+        .zip(&lives)
+        .any(|(life, start)| life < start));
+    assert!(
+        now.iter().cloned().fold(f64::MAX, f64::min)
+            < start.iter().cloned().fold(f64::MAX, f64::min)
+    );
+}
+
+// 僵尸会被打死、植物会被吃掉，关卡最终结束
+#[test]
+fn level_1_1_ends() {
+    let mut headless = common::battle(7);
+
+    headless.run_ticks_until(120 * 60);
+
+    let game = &headless.game;
+
+    assert_eq!(game.current_scene(), Some(SceneKind::Defeat));
+    assert!(game.zombie_count() < 5);
+    assert!(game.zombie_reached_house());
+}
+
+#[test]
+fn starts_at_home() {
+    let mut headless = common::load();
+
+    headless.start();
+
+    assert_eq!(headless.game.scenes, vec![SceneKind::Home]);
+}
+
+// 结算后点击回到主页，战场清空
+#[test]
+fn defeat_returns_home() {
+    let mut headless = common::battle(7);
+
+    headless.run_ticks_until(120 * 60);
+    assert_eq!(headless.game.current_scene(), Some(SceneKind::Defeat));
+
+    headless.click(&Pos::new(450.0, 300.0), 16.0);
+
+    let game = &headless.game;
+
+    assert_eq!(game.scenes, vec![SceneKind::Home]);
+    assert_eq!(game.zombie_count(), 0);
+    assert_eq!(game.sun, 150);
+    assert!(!game.state.zombies_spawned);
+    assert!(game.plant_cards.is_empty());
+}
+
+// 关闭页面时只保存战斗中（包括暂停）的对局
+#[test]
+fn only_battle_is_saved() {
+    let mut headless = common::battle(7);
+
+    assert!(headless.game.in_battle());
+
+    headless.game.pause();
+    assert!(headless.game.in_battle());
+
+    headless.game.resume();
+    headless.run_ticks_until(120 * 60);
+    assert!(!headless.game.in_battle());
+}
+
+#[test]
+fn same_seed_replays_identically() {
+    let run = || {
+        let mut headless = common::battle(42);
+
+        headless.run_for(15_000.0, 16.0);
+
+        serde_json::to_string(&headless.game.save_snapshot()).unwrap()
+    };
+
+    assert_eq!(run(), run());
+}
@@ -0,0 +1,49 @@
+// 集成测试共用：从仓库根目录加载资源，按固定种子开始关卡
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use plants_vs_zombies::{headless::Headless, scene::model::SceneKind};
+
+pub fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+pub fn load() -> Headless {
+    let mut headless = Headless::new();
+
+    headless.load(&root()).expect("failed to load assets");
+
+    headless
+}
+
+// 直接进入关卡 1-1 的战斗
+pub fn battle(seed: u64) -> Headless {
+    let mut headless = load();
+
+    headless.game.first_scene = SceneKind::Battle;
+    headless.game.set_seed(seed);
+    headless.start();
+
+    headless
+}
+
+pub fn zombie_lefts(headless: &Headless) -> Vec<f64> {
+    headless
+        .game
+        .sprites
+        .iter()
+        .filter(|sprite| sprite.is_zombie() && sprite.is_visible())
+        .map(|sprite| sprite.get_transform().pos.left)
+        .collect()
+}
+
+pub fn zombie_lives(headless: &Headless) -> Vec<f64> {
+    headless
+        .game
+        .sprites
+        .iter()
+        .filter(|sprite| sprite.is_zombie() && sprite.is_visible())
+        .filter_map(|sprite| sprite.sprite.health.map(|health| health.life))
+        .collect()
+}
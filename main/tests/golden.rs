@@ -0,0 +1,55 @@
+mod common;
+
+use std::{env, path::Path};
+
+use plants_vs_zombies::{
+    data::{CANVAS_HEIGHT, CANVAS_WIDTH},
+    headless::Headless,
+    renderer::software::{RgbaImage, SoftwareRenderer},
+    scene::model::SceneKind,
+};
+
+// 允许不同的像素数，以及每个通道的误差
+const MAX_DIFF: usize = 100;
+const TOLERANCE: u8 = 2;
+
+// 关卡 1-1 开始后第 ticks 个逻辑帧的画面；中间的帧不绘制，调试构建下也很快
+fn render(ticks: u64) -> RgbaImage {
+    let root = common::root();
+    let renderer = SoftwareRenderer::new(CANVAS_WIDTH, CANVAS_HEIGHT);
+    let frame = renderer.frame();
+    let mut headless = Headless::with_renderer(Box::new(renderer));
+
+    headless.load(&root).expect("failed to load assets");
+    headless.load_sheets(&root);
+    headless.game.set_seed(1);
+    headless.game.first_scene = SceneKind::Battle;
+    headless.start();
+    headless.run_ticks_until(ticks);
+    headless.game.draw_sprites(0.0);
+
+    let frame = frame.borrow().clone();
+
+    frame
+}
+
+// UPDATE_GOLDEN=1 cargo test --test golden 重新生成基准图
+#[test]
+fn level_1_1_matches_golden() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/level-1-1.png");
+    let frame = render(900);
+
+    if env::var("UPDATE_GOLDEN").is_ok() {
+        frame.save(&path).expect("failed to write golden image");
+    }
+
+    let golden = RgbaImage::open(&path).expect("failed to read golden image");
+    let diff = frame.diff(&golden, TOLERANCE);
+
+    assert!(
+        diff <= MAX_DIFF,
+        "{} pixels differ from {}",
+        diff,
+        path.display()
+    );
+}
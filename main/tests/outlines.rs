@@ -0,0 +1,35 @@
+mod common;
+
+use std::fs;
+
+use plants_vs_zombies::{headless::Headless, sprites::model::Pos};
+
+// 与 cargo run --bin build_outlines -- --check 相同：重新计算的轮廓与提交的文件一致
+#[test]
+fn checked_in_outlines_are_up_to_date() {
+    let root = common::root();
+    let manifest = Headless::manifest(&root).unwrap();
+    let mut headless = common::load();
+
+    headless.load_sheets(&root);
+
+    let sheets = headless.game.builder.build_outlines().unwrap();
+
+    assert_eq!(sheets.len(), manifest.outlines.len());
+
+    for (sheet_name, outlines) in sheets.iter() {
+        let file_name = format!("/{}-outline.json", sheet_name);
+        let path = manifest
+            .outlines
+            .iter()
+            .find(|path| path.ends_with(&file_name))
+            .unwrap_or_else(|| panic!("{} is not listed in the manifest", file_name));
+        let json = fs::read_to_string(root.join(path)).unwrap();
+
+        assert!(
+            Pos::write_outlines(outlines) == json,
+            "{} is out of date, run `cargo run --bin build_outlines`",
+            path
+        );
+    }
+}
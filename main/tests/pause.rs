@@ -0,0 +1,57 @@
+mod common;
+
+use plants_vs_zombies::{
+    headless::Headless,
+    model::{Interface, SpriteType},
+    sprites::model::Pos,
+};
+
+// 阳光与僵尸的位置
+fn positions(headless: &Headless) -> Vec<Pos> {
+    headless
+        .game
+        .sprites
+        .iter()
+        .filter(|sprite| {
+            sprite.is_zombie() || sprite.name() == SpriteType::Interface(Interface::Sun)
+        })
+        .filter(|sprite| sprite.is_visible())
+        .map(|sprite| sprite.get_transform().pos)
+        .collect()
+}
+
+// 暂停期间经过的时间不计入对局，恢复后不补算
+#[test]
+fn paused_time_is_not_caught_up() {
+    let mut paused = common::battle(7);
+    let mut control = common::battle(7);
+
+    paused.run_for(8_000.0, 16.0);
+    control.run_for(8_000.0, 16.0);
+
+    let ticks = paused.game.time_system.ticks;
+    let before = positions(&paused);
+
+    assert!(!before.is_empty());
+
+    paused.game.pause();
+    paused.step(10_000.0);
+
+    assert_eq!(paused.game.time_system.ticks, ticks);
+    assert_eq!(positions(&paused), before);
+
+    paused.game.resume();
+    paused.step(16.0);
+    control.step(16.0);
+
+    assert!(paused.game.time_system.ticks <= ticks + 1);
+    assert_eq!(
+        paused.game.time_system.ticks,
+        control.game.time_system.ticks
+    );
+    assert_eq!(positions(&paused), positions(&control));
+    assert_eq!(
+        paused.game.save_snapshot().to_json(),
+        control.game.save_snapshot().to_json()
+    );
+}
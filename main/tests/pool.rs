@@ -0,0 +1,85 @@
+mod common;
+
+use plants_vs_zombies::{
+    behavior::model::{BehaviorState, BehaviorType},
+    event::EventQueue,
+    game::Game,
+    polygon::Polygon,
+    rng::Rng,
+    scene::level::LevelScene,
+    sprites::{model::Pos, sprite_wrap::SpriteWrap},
+};
+
+fn acquire_sun(game: &mut Game, pos: &Pos) -> SpriteWrap {
+    let data = LevelScene::sun_data(&game.builder, &mut Rng::new(1), Some(pos));
+
+    game.pool.acquire(&game.builder, "interface", "Sun", data)
+}
+
+// 复用后、执行前的点击区域
+fn click_points(sun: &SpriteWrap) -> Vec<Pos> {
+    sun.behaviors
+        .iter()
+        .find_map(|behavior| match behavior.save() {
+            BehaviorState::Click { points, .. } => Some(points),
+            _ => None,
+        })
+        .unwrap()
+}
+
+// 与 Game 处理一次点击相同：启动 Click 行为后执行一个逻辑帧
+fn click(sun: &mut SpriteWrap, pos: &Pos) -> bool {
+    sun.toggle_behaviors(&[BehaviorType::Click], true, 0.0);
+    sun.update(0.0, 0.0, pos, &mut EventQueue::new());
+
+    sun.is_clicked()
+}
+
+#[test]
+fn reused_sun_is_clicked_at_its_new_position() {
+    let mut headless = common::load();
+    let game = &mut headless.game;
+    let old = Pos::new(100.0, 100.0);
+    let new = Pos::new(500.0, 300.0);
+    let inside = |pos: &Pos| Pos::new(pos.left + 30.0, pos.top + 30.0);
+    let mut sun = acquire_sun(game, &old);
+
+    assert!(click(&mut sun, &inside(&old)));
+
+    game.pool.release(sun);
+
+    let mut sun = acquire_sun(game, &new);
+
+    assert_eq!(game.pool.free_count(), 0);
+    assert_eq!(sun.get_transform().pos, new);
+
+    let points = click_points(&sun);
+
+    assert!(Polygon::contains(&points, &inside(&new)));
+    assert!(!Polygon::contains(&points, &inside(&old)));
+
+    assert!(!click(&mut sun, &inside(&old)));
+    assert!(click(&mut sun, &inside(&new)));
+}
+
+// 是否进池由精灵数据决定：带 Projectile 组件的子弹进池，植物不进池
+#[test]
+fn projectiles_are_pooled_by_data() {
+    let mut headless = common::load();
+    let game = &mut headless.game;
+    let bullet = game.builder.get_data("plant/PB00");
+    let plant = game.builder.get_data("plant/Peashooter");
+
+    assert!(bullet.projectile.is_some());
+
+    let bullet = game.pool.acquire(&game.builder, "plant", "PB00", bullet);
+    let plant = game
+        .pool
+        .acquire(&game.builder, "plant", "Peashooter", plant);
+
+    game.pool.release(plant);
+    assert_eq!(game.pool.free_count(), 0);
+
+    game.pool.release(bullet);
+    assert_eq!(game.pool.free_count(), 1);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use plants_vs_zombies::{
+    headless::Headless,
+    model::{Interface, SpriteType},
+    replay::Replay,
+    sprites::model::Pos,
+};
+
+// 当前可以点击收集的阳光中心
+fn suns(headless: &Headless) -> Vec<Pos> {
+    headless
+        .game
+        .sprites
+        .iter()
+        .filter(|sprite| {
+            sprite.name() == SpriteType::Interface(Interface::Sun) && sprite.is_visible()
+        })
+        .map(|sprite| {
+            let pos = sprite.get_transform().pos;
+
+            Pos::new(pos.left + 30.0, pos.top + 30.0)
+        })
+        .collect()
+}
+
+// 录制对局中的点击，导出后在新的 Game 上按相同种子与关卡回放，结果一致
+#[test]
+fn recorded_battle_replays_identically() {
+    let mut headless = common::battle(7);
+
+    headless.game.start_recording();
+
+    for _ in 0..6 {
+        headless.run_for(4_000.0, 16.0);
+
+        for pos in suns(&headless) {
+            headless.click(&pos, 16.0);
+        }
+    }
+
+    headless.run_for(2_000.0, 16.0);
+
+    // 收集到了阳光，录像中的点击确实改变了对局
+    assert!(headless.game.sun > 150);
+
+    let json = headless.game.stop_recording().unwrap().to_json();
+    let replay = Replay::new_from_json(&json).unwrap();
+    let mut replayed = common::load();
+
+    replayed.replay(&replay).unwrap();
+
+    assert_eq!(replayed.game.time_system.ticks, replay.ticks);
+    assert_eq!(
+        replayed.game.save_snapshot().to_json(),
+        headless.game.save_snapshot().to_json()
+    );
+}
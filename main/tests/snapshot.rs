@@ -0,0 +1,71 @@
+mod common;
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use plants_vs_zombies::{headless::Headless, snapshot::Snapshot};
+
+const SAVED_AT: u64 = 30 * 60;
+const RUN_TO: u64 = 45 * 60;
+
+fn fixture() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/level-1-1-30s.json")
+}
+
+// 在全新的 Game 上恢复快照，不调用 start
+fn restore(snapshot: &Snapshot) -> Headless {
+    let mut headless = common::load();
+
+    headless
+        .resume(snapshot)
+        .expect("failed to restore snapshot");
+
+    headless
+}
+
+fn snapshot_json(headless: &Headless) -> String {
+    headless.game.save_snapshot().to_json()
+}
+
+// 战斗中途保存后恢复，两边继续推进相同的逻辑帧，状态一致
+#[test]
+fn restored_battle_continues_identically() {
+    let mut original = common::battle(7);
+
+    original.run_ticks_until(SAVED_AT);
+
+    let snapshot = original.game.save_snapshot();
+    let mut restored = restore(&snapshot);
+
+    assert_eq!(snapshot_json(&restored), snapshot.to_json());
+
+    original.run_ticks_until(RUN_TO);
+    restored.run_ticks_until(RUN_TO);
+
+    assert_eq!(snapshot_json(&restored), snapshot_json(&original));
+}
+
+// UPDATE_SNAPSHOT=1 cargo test --test snapshot 重新生成快照文件
+#[test]
+fn checked_in_snapshot_continues_identically() {
+    let mut original = common::battle(7);
+
+    original.run_ticks_until(SAVED_AT);
+
+    if env::var("UPDATE_SNAPSHOT").is_ok() {
+        fs::write(fixture(), snapshot_json(&original)).expect("failed to write snapshot");
+    }
+
+    let json = fs::read_to_string(fixture()).expect("failed to read snapshot");
+    let snapshot = Snapshot::new_from_json(&json).expect("invalid snapshot");
+    let mut restored = restore(&snapshot);
+
+    assert_eq!(snapshot.ticks, SAVED_AT);
+
+    original.run_ticks_until(RUN_TO);
+    restored.run_ticks_until(RUN_TO);
+
+    assert_eq!(snapshot_json(&restored), snapshot_json(&original));
+}
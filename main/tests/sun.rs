@@ -0,0 +1,59 @@
+mod common;
+
+use plants_vs_zombies::{rng::Rng, scene::level::LevelScene, sprites::model::Pos};
+
+// 天上掉落的阳光：起点与下落距离
+fn drops(seed: u64, count: usize) -> Vec<(Pos, f64)> {
+    let headless = common::load();
+    let mut rng = Rng::new(seed);
+
+    (0..count)
+        .map(|_| {
+            let data = LevelScene::sun_data(&headless.game.builder, &mut rng, None);
+
+            (data.pos[0], data.behaviors[2].distance)
+        })
+        .collect()
+}
+
+#[test]
+fn seeded_sun_drops_are_exact() {
+    let expected = [
+        (Pos::new(100.0, -56.0), 325.0),
+        (Pos::new(430.0, -53.0), 333.0),
+        (Pos::new(298.0, -63.0), 407.0),
+        (Pos::new(314.0, -60.0), 533.0),
+        (Pos::new(569.0, -37.0), 535.0),
+    ];
+
+    assert_eq!(drops(7, 5), expected);
+    assert_eq!(drops(7, 5), drops(7, 5));
+}
+
+#[test]
+fn different_seeds_drop_differently() {
+    assert_ne!(drops(7, 5), drops(8, 5));
+}
+
+// 种子 0 会让 xorshift 全为 0，换成默认种子
+#[test]
+fn zero_seed_uses_default_seed() {
+    let mut zero = Rng::new(0);
+    let mut default = Rng::default();
+
+    assert_eq!(zero.seed(), 0);
+    assert!((0..10).all(|_| zero.next_u64() == default.next_u64()));
+}
+
+// 向日葵产出的阳光落在指定位置，不消耗随机数
+#[test]
+fn produced_sun_does_not_use_rng() {
+    let headless = common::load();
+    let mut rng = Rng::new(7);
+    let pos = Pos::new(300.0, 200.0);
+    let data = LevelScene::sun_data(&headless.game.builder, &mut rng, Some(&pos));
+
+    assert_eq!(data.pos[0], pos);
+    assert_eq!(data.behaviors[2].distance, 50.0);
+    assert_eq!(rng.next_u64(), Rng::new(7).next_u64());
+}
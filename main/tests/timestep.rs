@@ -0,0 +1,63 @@
+mod common;
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use plants_vs_zombies::{
+    headless::Headless,
+    time_system::{MAX_TICKS_PER_FRAME, TICK_DURATION},
+};
+
+fn digest(headless: &Headless) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    headless.game.save_snapshot().to_json().hash(&mut hasher);
+
+    hasher.finish()
+}
+
+// 按 frame 毫秒一帧运行 duration 毫秒，记录每个逻辑帧结束时的状态
+fn run(frame: f64, duration: f64) -> HashMap<u64, u64> {
+    let mut headless = common::battle(7);
+    let mut states = HashMap::new();
+    let mut elapsed = 0.0;
+
+    while elapsed < duration {
+        headless.step(frame);
+        elapsed += frame;
+        states.insert(headless.game.time_system.ticks, digest(&headless));
+    }
+
+    states
+}
+
+// 渲染帧率不同，同一逻辑帧的状态完全一致
+#[test]
+fn frame_rate_does_not_change_the_battle() {
+    // 16 毫秒一帧时每帧最多一个逻辑帧，每个逻辑帧都有记录；多运行一秒覆盖慢的一方
+    let fast = run(16.0, 11_000.0);
+    let slow = run(50.0, 10_000.0);
+
+    assert!(slow.len() > 150);
+
+    for (tick, state) in slow {
+        assert_eq!(fast.get(&tick), Some(&state), "tick {} differs", tick);
+    }
+}
+
+// 长时间卡顿后单次渲染最多追赶 MAX_TICKS_PER_FRAME 个逻辑帧，不补算剩余时间
+#[test]
+fn long_frames_are_clamped() {
+    let mut headless = common::battle(7);
+
+    headless.step(10_000.0);
+    assert_eq!(headless.game.time_system.ticks, MAX_TICKS_PER_FRAME as u64);
+
+    headless.step(TICK_DURATION * 1.5);
+    assert_eq!(
+        headless.game.time_system.ticks,
+        MAX_TICKS_PER_FRAME as u64 + 1
+    );
+}
@@ -0,0 +1,102 @@
+use std::{fs, path::Path};
+
+use plants_vs_zombies::{
+    builder::Builder,
+    manifest::AssetError,
+    model::{LevelData, SpriteType},
+    sprites::model::{SpriteCell, SpriteData},
+};
+
+fn read(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/validate")
+        .join(name);
+
+    fs::read_to_string(path).unwrap()
+}
+
+// data 中的精灵数据覆盖 data.json 中的同名数据
+fn validate(data: &str, level: &str) -> Vec<AssetError> {
+    let mut builder = Builder::new();
+
+    builder
+        .cells
+        .extend(SpriteCell::new_from_json(&read("cells.json")).unwrap());
+    builder
+        .data
+        .extend(SpriteData::new_from_json(&read("data.json")).unwrap());
+    builder
+        .data
+        .extend(SpriteData::new_from_json(&read(data)).unwrap());
+
+    builder.validate(&LevelData::new_from_json(&read(level)).unwrap())
+}
+
+#[test]
+fn valid_assets_have_no_errors() {
+    assert_eq!(validate("data.json", "level.json"), vec![]);
+}
+
+#[test]
+fn reports_missing_switch_cells() {
+    assert_eq!(
+        validate("missing-switch-cell.json", "level.json"),
+        vec![AssetError::MissingCells {
+            key: "plant/SunFlower2".to_string(),
+            by: "plant/SunFlower1".to_string(),
+        }]
+    );
+}
+
+// 缺少 interval 时报告错误，而不是在创建精灵时 panic
+#[test]
+fn reports_missing_interval() {
+    let errors = validate("missing-interval.json", "level.json");
+
+    assert_eq!(
+        errors,
+        vec![AssetError::MissingField {
+            field: "interval".to_string(),
+            by: "plant/Peashooter".to_string(),
+        }]
+    );
+    assert!(errors[0].to_string().contains("plant/Peashooter"));
+}
+
+#[test]
+fn reports_unknown_plants() {
+    let errors = validate("data.json", "unknown-plant.json");
+
+    assert!(errors.contains(&AssetError::UnknownSprite {
+        name: "Dandelion".to_string(),
+        by: "level unknown".to_string(),
+    }));
+}
+
+#[test]
+fn reports_plants_outside_the_lawn() {
+    assert_eq!(
+        validate("data.json", "out-of-lawn.json"),
+        vec![
+            AssetError::OutOfLawn {
+                name: "Peashooter".to_string(),
+                row: 5,
+                col: 3,
+                by: "level outside".to_string(),
+            },
+            AssetError::OutOfLawn {
+                name: "SunFlower1".to_string(),
+                row: 0,
+                col: 20,
+                by: "level outside".to_string(),
+            },
+        ]
+    );
+}
+
+// 新的植物只需要帧数据与精灵数据
+#[test]
+fn new_plants_need_only_data() {
+    assert_eq!(validate("new-plant.json", "unknown-plant.json"), vec![]);
+    assert_eq!(SpriteType::new("plant", "Dandelion"), SpriteType::Plant);
+}